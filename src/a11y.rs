@@ -0,0 +1,96 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlElement, KeyboardEvent};
+
+/// Elements that take focus from Tab by default
+const FOCUSABLE_SELECTOR: &str = "a[href], area[href], button:not([disabled]), \
+    input:not([disabled]):not([type=\"hidden\"]), select:not([disabled]), \
+    textarea:not([disabled]), [tabindex]:not([tabindex=\"-1\"]), [contenteditable=\"true\"]";
+
+/// Keeps Tab/Shift-Tab cycling within a container while alive. Dropping it removes the keydown
+/// listener and returns focus to whatever had it before the trap was created
+#[must_use = "the focus trap is released as soon as it's dropped"]
+pub struct FocusTrap {
+    document: Document,
+    keydown: Closure<dyn FnMut(KeyboardEvent)>,
+    previously_focused: Option<HtmlElement>,
+}
+
+impl Drop for FocusTrap {
+    fn drop(&mut self) {
+        let _ = self
+            .document
+            .remove_event_listener_with_callback("keydown", self.keydown.as_ref().unchecked_ref());
+
+        if let Some(element) = &self.previously_focused {
+            let _ = element.focus();
+        }
+    }
+}
+
+/// Traps keyboard focus inside `container` (e.g. an open modal), focusing its first focusable
+/// descendant. Focusable elements are looked up on every Tab so content can change while trapped
+pub fn focus_trap(container: &HtmlElement) -> Result<FocusTrap, JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document"))?;
+    let previously_focused = active_element();
+
+    let trap_container = container.clone();
+    let keydown = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if event.key() != "Tab" {
+            return;
+        }
+
+        let focusable = focusable_elements(&trap_container);
+        let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else {
+            // Nothing to move to, so don't let focus leave either
+            event.prevent_default();
+            return;
+        };
+
+        let active = active_element();
+        let outside = active
+            .as_ref()
+            .is_none_or(|active| !trap_container.contains(Some(active.as_ref())));
+
+        if event.shift_key() && (outside || active.as_ref() == Some(first)) {
+            event.prevent_default();
+            let _ = last.focus();
+        } else if !event.shift_key() && (outside || active.as_ref() == Some(last)) {
+            event.prevent_default();
+            let _ = first.focus();
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+
+    // Listen on the document so a Tab from outside the container is pulled back in too
+    document.add_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref())?;
+
+    if let Some(first) = focusable_elements(container).first() {
+        first.focus()?;
+    }
+
+    Ok(FocusTrap {
+        document,
+        keydown,
+        previously_focused,
+    })
+}
+
+fn focusable_elements(container: &HtmlElement) -> Vec<HtmlElement> {
+    let Ok(nodes) = container.query_selector_all(FOCUSABLE_SELECTOR) else {
+        return Vec::new();
+    };
+
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index)?.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
+
+fn active_element() -> Option<HtmlElement> {
+    web_sys::window()?
+        .document()?
+        .active_element()?
+        .dyn_into::<HtmlElement>()
+        .ok()
+}
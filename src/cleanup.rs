@@ -0,0 +1,13 @@
+use leptos::prelude::on_cleanup;
+use send_wrapper::SendWrapper;
+
+/// `on_cleanup` for cleanups that hold browser handles (closures, elements, observers) which
+/// aren't `Send` or `Sync`. Wasm only ever has the one thread, so the cleanup is wrapped to
+/// satisfy `on_cleanup`'s bounds and always runs on the thread that registered it
+pub fn on_cleanup_local<F>(cleanup: F)
+where
+    F: FnOnce() + 'static,
+{
+    let cleanup = SendWrapper::new(cleanup);
+    on_cleanup(move || cleanup.take()());
+}
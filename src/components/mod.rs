@@ -4,6 +4,8 @@ pub mod form;
 pub mod icons;
 pub mod overlay;
 pub mod page;
+pub mod scroll_reveal;
 pub mod scrollable;
 pub mod seo;
+pub mod sprite_scroller;
 pub mod toast;
@@ -0,0 +1,40 @@
+use crate::intersection_observer::{IntersectionConfig, Intersectioner};
+use leptos::{html::Div, prelude::*};
+use web_sys::Element;
+
+/// Adds `animation_class` to its wrapper once it scrolls into view. With `once = false` the class
+/// is removed again when the wrapper leaves the viewport so the animation replays
+#[component]
+pub fn ScrollReveal(
+    children: Children,
+    animation_class: &'static str,
+    #[prop(default = 0.1)] threshold: f64,
+    #[prop(default = true)] once: bool,
+) -> impl IntoView {
+    let revealed = RwSignal::new(false);
+    let reveal_ref = NodeRef::<Div>::new();
+
+    reveal_ref.on_load(move |element| {
+        let element: Element = element.into();
+        let observer = Intersectioner::new(IntersectionConfig::new().threshold(threshold));
+
+        if once {
+            observer.observe(&element, true, move || revealed.set(true));
+        } else {
+            observer.observe_with_exit(
+                &element,
+                move || revealed.set(true),
+                move || revealed.set(false),
+            );
+        }
+
+        // Dropping the observer disconnects it, so tie it to the component's lifetime
+        let _ = StoredValue::new_local(observer);
+    });
+
+    view! {
+        <div node_ref=reveal_ref class=(animation_class, move || revealed.get())>
+            {children()}
+        </div>
+    }
+}
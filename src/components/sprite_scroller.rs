@@ -0,0 +1,36 @@
+use crate::scroll_storyteller::{frame_index_at, ScrollProgress};
+use leptos::prelude::*;
+use web_sys::HtmlImageElement;
+
+/// Plays an image sequence by scroll, showing the frame of `frames` that `progress` falls on,
+/// e.g. a product rotating as the page scrolls. Every frame is preloaded on mount, and `src` is
+/// only rewritten when the frame actually changes
+#[component]
+pub fn SpriteScroller(
+    /// Image URLs in playback order
+    frames: Vec<String>,
+    /// Usually a storyteller's `progress()`
+    #[prop(into)]
+    progress: Signal<ScrollProgress>,
+    #[prop(optional)] alt: &'static str,
+    #[prop(optional)] class: &'static str,
+) -> impl IntoView {
+    let frame_count = frames.len();
+
+    // Keep the preloading images alive so the browser doesn't drop them before they're cached
+    let preloaded = frames
+        .iter()
+        .filter_map(|url| {
+            let image = HtmlImageElement::new().ok()?;
+            image.set_src(url);
+            Some(image)
+        })
+        .collect::<Vec<_>>();
+    let _ = StoredValue::new_local(preloaded);
+
+    // A memo only notifies when the index changes, so scroll events within a frame skip the DOM
+    let frame = Memo::new(move |_| frame_index_at(progress.get().progress, frame_count));
+    let src = move || frames.get(frame.get()).cloned().unwrap_or_default();
+
+    view! { <img src=src alt=alt class=class /> }
+}
@@ -1,40 +1,167 @@
-use web_sys::{wasm_bindgen::JsCast, window, HtmlDocument};
+use std::fmt;
+
+use web_sys::{
+    wasm_bindgen::{JsCast, JsValue},
+    window, HtmlDocument,
+};
 // TODO: SPLIT UTILS INTO SEPARATE CRATE AND USE
 
-// Function to set a cookie securely
-pub fn set_secure_cookie(name: &str, value: &str, path: Option<&str>) {
-    let document = window()
-        .unwrap()
-        .document()
-        .unwrap()
-        .unchecked_into::<HtmlDocument>();
-    // Build the cookie string
-    let mut cookie_string = name.to_string() + "=" + value;
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SameSite {
+    #[default]
+    Strict,
+    Lax,
+    /// Only honoured by browsers alongside `Secure`
+    None,
+}
 
-    if let Some(path) = path {
-        cookie_string.push_str(&("; Path={}".to_string() + path));
+impl SameSite {
+    fn as_str(self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
     }
+}
+
+/// Why a cookie couldn't be set
+#[derive(Debug, Clone)]
+pub enum CookieError {
+    /// Assigning `document.cookie` threw, e.g. in a sandboxed iframe
+    Js(JsValue),
+    /// The browser ignored the cookie without an error. Usually `Secure` on a plain `http://`
+    /// origin, a cookie over the size limit, or an existing `HttpOnly` cookie with the same name
+    Rejected,
+}
+
+impl fmt::Display for CookieError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CookieError::Js(error) => write!(f, "Setting the cookie failed: {:?}", error),
+            CookieError::Rejected => write!(
+                f,
+                "The browser rejected the cookie, set CookieOptions::secure to false over http"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CookieError {}
+
+impl From<JsValue> for CookieError {
+    fn from(error: JsValue) -> Self {
+        CookieError::Js(error)
+    }
+}
+
+/// Attributes for `set_cookie_with_options`
+#[derive(Clone, PartialEq, Debug)]
+pub struct CookieOptions {
+    pub path: Option<String>,
+    pub same_site: SameSite,
+    /// Lifetime in seconds, a session cookie when `None`. 0 or less expires it immediately
+    pub max_age: Option<i64>,
+    /// Only send the cookie over https, true by default. Turn it off for local development over
+    /// plain `http://`, where some browsers drop `Secure` cookies, e.g.
+    /// `secure: cfg!(not(debug_assertions))`
+    pub secure: bool,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self {
+            path: None,
+            same_site: SameSite::default(),
+            max_age: None,
+            secure: true,
+        }
+    }
+}
+
+/// Builds the `document.cookie` assignment for `name=value` with `options`' attributes
+pub fn build_cookie_string(name: &str, value: &str, options: &CookieOptions) -> String {
+    let mut cookie_string = format!("{}={}", name, value);
+
+    if let Some(path) = &options.path {
+        cookie_string.push_str(&format!("; Path={}", path));
+    }
+
+    if let Some(max_age) = options.max_age {
+        cookie_string.push_str(&format!("; Max-Age={}", max_age));
+    }
+
+    if options.secure {
+        cookie_string.push_str("; Secure");
+    }
+
+    cookie_string.push_str(&format!("; SameSite={}", options.same_site.as_str()));
 
-    cookie_string.push_str("; Secure");
+    cookie_string
+}
+
+// Function to set a cookie securely
+pub fn set_secure_cookie(name: &str, value: &str, path: Option<&str>) -> Result<(), CookieError> {
+    let options = CookieOptions {
+        path: path.map(str::to_string),
+        ..Default::default()
+    };
+
+    set_cookie_with_options(name, value, &options)
+}
+
+/// Does nothing where there's no document, e.g. during server-side rendering. Browsers drop
+/// cookies they won't store without throwing, so the cookie is read back to report that as
+/// `CookieError::Rejected`
+pub fn set_cookie_with_options(
+    name: &str,
+    value: &str,
+    options: &CookieOptions,
+) -> Result<(), CookieError> {
+    let Some(document) = html_document() else {
+        return Ok(());
+    };
 
-    cookie_string.push_str("; SameSite=Strict");
+    document.set_cookie(&build_cookie_string(name, value, options))?;
+
+    // A non-positive Max-Age deletes the cookie, and a cookie for another path isn't visible
+    // from this page, so neither can be read back
+    let is_deletion = options.max_age.is_some_and(|max_age| max_age <= 0);
+    let is_visible = options.path.as_deref().is_none_or(|path| {
+        window()
+            .and_then(|window| window.location().pathname().ok())
+            .is_some_and(|pathname| pathname.starts_with(path))
+    });
+    if !is_deletion && is_visible && get_cookie(name).as_deref() != Some(value) {
+        return Err(CookieError::Rejected);
+    }
 
-    document.set_cookie(&cookie_string).unwrap()
+    Ok(())
 }
 
+/// `None` where there's no document, e.g. during server-side rendering
 pub fn get_cookie(name: &str) -> Option<String> {
-    let document = window()
-        .unwrap()
-        .document()
-        .unwrap()
-        .unchecked_into::<HtmlDocument>();
+    let cookies = html_document()?.cookie().ok()?;
 
-    let cookies = document.cookie().unwrap();
+    find_cookie(&cookies, name)
+}
+
+fn html_document() -> Option<HtmlDocument> {
+    Some(window()?.document()?.unchecked_into::<HtmlDocument>())
+}
 
+/// Whether a cookie called `name` is set, including bare flags (`flag`) and empty values (`flag=`)
+pub fn has_cookie(name: &str) -> bool {
+    get_cookie(name).is_some()
+}
+
+/// Finds `name` in a `document.cookie` string. Bare flags with no `=` are present with an empty
+/// value
+fn find_cookie(cookies: &str, name: &str) -> Option<String> {
     cookies.split(';').map(|s| s.trim()).find_map(|cookie| {
         let mut parts = cookie.splitn(2, '=');
         let key = parts.next()?.trim();
-        let value = parts.next()?.trim();
+        let value = parts.next().unwrap_or_default().trim();
         if key == name {
             Some(value.to_string())
         } else {
@@ -42,3 +169,66 @@ pub fn get_cookie(name: &str) -> Option<String> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{build_cookie_string, find_cookie, CookieOptions, SameSite};
+
+    #[test]
+    fn bare_flag_is_present_and_empty() {
+        assert_eq!(find_cookie("theme=dark; flag", "flag"), Some(String::new()));
+    }
+
+    #[test]
+    fn empty_value_is_present_and_empty() {
+        assert_eq!(
+            find_cookie("flag=; theme=dark", "flag"),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn value_is_returned() {
+        assert_eq!(
+            find_cookie("theme=dark; flag=value", "flag"),
+            Some("value".to_string())
+        );
+        assert_eq!(find_cookie("theme=dark", "flag"), None);
+    }
+
+    #[test]
+    fn default_cookie_is_secure_and_strict() {
+        assert_eq!(
+            build_cookie_string("auth-token", "abc", &CookieOptions::default()),
+            "auth-token=abc; Secure; SameSite=Strict"
+        );
+    }
+
+    #[test]
+    fn insecure_cookie_omits_secure() {
+        let options = CookieOptions {
+            secure: false,
+            ..CookieOptions::default()
+        };
+
+        assert_eq!(
+            build_cookie_string("auth-token", "abc", &options),
+            "auth-token=abc; SameSite=Strict"
+        );
+    }
+
+    #[test]
+    fn cookie_string_includes_every_attribute() {
+        let options = CookieOptions {
+            path: Some("/app".to_string()),
+            same_site: SameSite::Lax,
+            max_age: Some(3600),
+            secure: true,
+        };
+
+        assert_eq!(
+            build_cookie_string("auth-token", "abc", &options),
+            "auth-token=abc; Path=/app; Max-Age=3600; Secure; SameSite=Lax"
+        );
+    }
+}
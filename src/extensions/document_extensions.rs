@@ -4,6 +4,11 @@ pub trait DocumentExtensions {
     fn get_element_by_id_unchecked<T>(&self, element_id: &str) -> Option<T>
     where
         T: JsCast;
+
+    /// Creates a `tag` element as `T` without checking it is one, `None` if the tag is invalid
+    fn create_element_unchecked<T>(&self, tag: &str) -> Option<T>
+    where
+        T: JsCast;
 }
 
 impl DocumentExtensions for Document {
@@ -14,4 +19,13 @@ impl DocumentExtensions for Document {
         self.get_element_by_id(element_id)
             .map(|el| el.unchecked_into::<T>())
     }
+
+    fn create_element_unchecked<T>(&self, tag: &str) -> Option<T>
+    where
+        T: JsCast,
+    {
+        self.create_element(tag)
+            .ok()
+            .map(|el| el.unchecked_into::<T>())
+    }
 }
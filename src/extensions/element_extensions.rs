@@ -1,15 +1,35 @@
 use web_sys::{window, Element, ScrollBehavior, ScrollToOptions};
 
+/// Where `scroll_into_view_if_needed` places an element that isn't fully visible
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ScrollAlign {
+    /// Move the least distance that brings the element into view
+    #[default]
+    Nearest,
+    Center,
+}
+
 pub trait ElementExtensions {
     fn scroll_to_with_offset(&self, offset: f64);
     fn scroll_element_to_with_offset(&self, element: Element, offset: f64);
+    /// Scrolls `container` (or the page when `None`) only if the element isn't already fully
+    /// visible below `offset`, avoiding jarring movement for elements that are in view
+    fn scroll_into_view_if_needed(
+        &self,
+        container: Option<Element>,
+        offset: f64,
+        align: ScrollAlign,
+    );
 }
 
 impl ElementExtensions for web_sys::Element {
     fn scroll_to_with_offset(&self, offset: f64) {
-        let window = window().unwrap();
+        // Nothing to scroll without a window, e.g. during server-side rendering
+        let Some(window) = window() else {
+            return;
+        };
         let rect = self.get_bounding_client_rect();
-        let target_position = rect.top() + window.scroll_y().unwrap() - offset;
+        let target_position = rect.top() + window.scroll_y().unwrap_or_default() - offset;
         let scroll_options = ScrollToOptions::new();
 
         scroll_options.set_top(target_position);
@@ -29,4 +49,117 @@ impl ElementExtensions for web_sys::Element {
         scroll_options.set_behavior(ScrollBehavior::Smooth);
         element.scroll_to_with_scroll_to_options(&scroll_options);
     }
+
+    fn scroll_into_view_if_needed(
+        &self,
+        container: Option<Element>,
+        offset: f64,
+        align: ScrollAlign,
+    ) {
+        let Some(window) = window() else {
+            return;
+        };
+        let rect = self.get_bounding_client_rect();
+
+        let (view_top, view_bottom) = match &container {
+            Some(container) => {
+                let top =
+                    container.get_bounding_client_rect().top() + container.client_top() as f64;
+                (top, top + container.client_height() as f64)
+            }
+            None => {
+                let height = window
+                    .inner_height()
+                    .ok()
+                    .and_then(|height| height.as_f64())
+                    .unwrap_or_default();
+                (0.0, height)
+            }
+        };
+
+        let Some(delta) = scroll_delta_if_needed(
+            rect.top(),
+            rect.bottom(),
+            view_top + offset,
+            view_bottom,
+            align,
+        ) else {
+            return;
+        };
+
+        let scroll_options = ScrollToOptions::new();
+        scroll_options.set_top(delta);
+        scroll_options.set_behavior(ScrollBehavior::Smooth);
+        match container {
+            Some(container) => container.scroll_by_with_scroll_to_options(&scroll_options),
+            None => window.scroll_by_with_scroll_to_options(&scroll_options),
+        }
+    }
+}
+
+/// How far to scroll so `[top, bottom]` sits within `[view_top, view_bottom]`, `None` when it
+/// already does. An element taller than the view is aligned to its top under `Nearest`
+fn scroll_delta_if_needed(
+    top: f64,
+    bottom: f64,
+    view_top: f64,
+    view_bottom: f64,
+    align: ScrollAlign,
+) -> Option<f64> {
+    if top >= view_top && bottom <= view_bottom {
+        return None;
+    }
+
+    let delta = match align {
+        ScrollAlign::Center => (top + bottom) / 2.0 - (view_top + view_bottom) / 2.0,
+        ScrollAlign::Nearest if top < view_top || bottom - top > view_bottom - view_top => {
+            top - view_top
+        }
+        ScrollAlign::Nearest => bottom - view_bottom,
+    };
+    Some(delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scroll_delta_if_needed, ScrollAlign};
+
+    #[test]
+    fn fully_visible_element_doesnt_scroll() {
+        assert_eq!(
+            scroll_delta_if_needed(100.0, 200.0, 0.0, 800.0, ScrollAlign::Nearest),
+            None
+        );
+        assert_eq!(
+            scroll_delta_if_needed(100.0, 200.0, 0.0, 800.0, ScrollAlign::Center),
+            None
+        );
+    }
+
+    #[test]
+    fn nearest_moves_the_least_distance() {
+        // Partly below the view
+        assert_eq!(
+            scroll_delta_if_needed(750.0, 850.0, 0.0, 800.0, ScrollAlign::Nearest),
+            Some(50.0)
+        );
+        // Partly under a 64px header
+        assert_eq!(
+            scroll_delta_if_needed(40.0, 140.0, 64.0, 800.0, ScrollAlign::Nearest),
+            Some(-24.0)
+        );
+        // Taller than the view aligns its top
+        assert_eq!(
+            scroll_delta_if_needed(500.0, 1500.0, 0.0, 800.0, ScrollAlign::Nearest),
+            Some(500.0)
+        );
+    }
+
+    #[test]
+    fn center_aligns_midpoints() {
+        assert_eq!(
+            scroll_delta_if_needed(900.0, 1000.0, 0.0, 800.0, ScrollAlign::Center),
+            Some(550.0)
+        );
+    }
 }
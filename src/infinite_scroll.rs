@@ -1,50 +1,385 @@
 use std::future::Future;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
+use gloo_timers::future::TimeoutFuture;
+use leptos::ev::scroll;
+use leptos::prelude::{on_cleanup, window_event_listener};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{wasm_bindgen::JsCast, Event, HtmlElement};
 
+/// Most loads run back to back to fill a container that's still short of the threshold, in case
+/// every load changes the height without ever making it scrollable
+const MAX_FILL_LOADS: u32 = 10;
+
+/// How close to the end of the content the next load starts
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LoadThreshold {
+    /// Fraction of the scrollable distance, which grows with the list
+    Fraction(f64),
+    /// Fixed distance in px, for the same prefetch distance however long the list gets
+    Pixels(f64),
+}
+
+impl Default for LoadThreshold {
+    fn default() -> Self {
+        LoadThreshold::Fraction(0.1)
+    }
+}
+
+impl From<f64> for LoadThreshold {
+    fn from(fraction: f64) -> Self {
+        LoadThreshold::Fraction(fraction)
+    }
+}
+
+impl LoadThreshold {
+    /// Distance in px from the end of `max_scroll` at which to load
+    #[inline(always)]
+    fn distance(self, max_scroll: f64) -> f64 {
+        match self {
+            LoadThreshold::Fraction(fraction) => max_scroll * fraction,
+            LoadThreshold::Pixels(px) => px,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct InfiniteScrollConfig {
+    /// Time after a load completes during which new triggers are ignored, so a fast flick right
+    /// as a load finishes doesn't queue a second one. 0 disables the cooldown
+    pub min_interval_ms: u32,
+    /// Where the container variants start loading, 10% of the scrollable distance by default.
+    /// `setup_window_infinite_scroll` takes its threshold as an argument instead
+    pub threshold: LoadThreshold,
+}
+
+/// Infers the scroll container from the event's target. Prefer
+/// `setup_infinite_scroll_with_container` when the listener isn't on the container itself, since
+/// bubbled events would report a child instead
 pub fn setup_infinite_scroll<E, F, Fut>(on_scroll_end: F) -> impl Fn(E)
+where
+    E: AsRef<Event> + Clone + 'static,
+    F: Fn(E) -> Fut + Clone + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    setup_infinite_scroll_with_config(InfiniteScrollConfig::default(), on_scroll_end)
+}
+
+pub fn setup_infinite_scroll_with_config<E, F, Fut>(
+    config: InfiniteScrollConfig,
+    on_scroll_end: F,
+) -> impl Fn(E)
 where
     E: AsRef<Event> + Clone + 'static,
     F: Fn(E) -> Fut + Clone + 'static,
     Fut: Future<Output = ()> + 'static,
 {
     // Create state that persists across scroll events
-    let is_running = Rc::new(RefCell::new(false));
+    let guard = LoadGuard::new(config);
 
     move |event: E| {
-        let is_running = Rc::clone(&is_running);
-
-        // Early return if already loading
-        if *is_running.borrow() {
+        // Early return if already loading or cooling down
+        if !guard.is_ready() {
             return;
         }
 
-        let element = event
+        let Some(element) = event
             .as_ref()
             .target()
-            .unwrap()
-            .unchecked_into::<HtmlElement>();
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        else {
+            return;
+        };
 
-        let element_height = element.scroll_height() - element.offset_height();
-        let threshold_height = element_height - (element_height as f32 * 0.1) as i32;
+        let area = ScrollArea::Element(element);
+        if area.is_near_bottom(config.threshold) {
+            let on_scroll_end = on_scroll_end.clone();
+            let event = event.clone();
+            guard.start(area, config.threshold, move || on_scroll_end(event.clone()));
+        }
+    }
+}
 
-        if element.scroll_top() >= threshold_height {
-            // Set loading flag
-            *is_running.borrow_mut() = true;
+/// Like `setup_infinite_scroll`, but measures `container` rather than the event's target
+pub fn setup_infinite_scroll_with_container<E, F, Fut>(
+    container: HtmlElement,
+    config: Option<InfiniteScrollConfig>,
+    on_scroll_end: F,
+) -> impl Fn(E)
+where
+    E: AsRef<Event> + Clone + 'static,
+    F: Fn(E) -> Fut + Clone + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    let config = config.unwrap_or_default();
+    let guard = LoadGuard::new(config);
+
+    move |event: E| {
+        if !guard.is_ready() {
+            return;
+        }
 
-            // Clone the closure for async context
+        let area = ScrollArea::Element(container.clone());
+        if area.is_near_bottom(config.threshold) {
             let on_scroll_end = on_scroll_end.clone();
             let event = event.clone();
+            guard.start(area, config.threshold, move || on_scroll_end(event.clone()));
+        }
+    }
+}
 
-            spawn_local(async move {
-                // Execute the async loading
-                on_scroll_end(event).await;
+/// Loads more as the page itself nears the bottom, for layouts that scroll the window rather than
+/// a container. `threshold` is how close to the end `on_scroll_end` runs, e.g. `0.1` for the
+/// last 10% or `LoadThreshold::Pixels(600.0)`. The listener is removed when the calling
+/// component unmounts
+pub fn setup_window_infinite_scroll<F, Fut>(
+    threshold: impl Into<LoadThreshold>,
+    config: Option<InfiniteScrollConfig>,
+    on_scroll_end: F,
+) where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    let threshold = threshold.into();
+    let guard = LoadGuard::new(config.unwrap_or_default());
+    let on_scroll_end = Rc::new(on_scroll_end);
+
+    let handle = window_event_listener(scroll, move |_| {
+        if !guard.is_ready() {
+            return;
+        }
+
+        if ScrollArea::Window.is_near_bottom(threshold) {
+            let on_scroll_end = on_scroll_end.clone();
+            guard.start(ScrollArea::Window, threshold, move || on_scroll_end());
+        }
+    });
+
+    on_cleanup(move || handle.remove());
+}
+
+/// What an infinite scroll loads into, measured fresh on every check
+enum ScrollArea {
+    Element(HtmlElement),
+    Window,
+}
+
+impl ScrollArea {
+    fn scroll_height(&self) -> f64 {
+        match self {
+            ScrollArea::Element(element) => element.scroll_height() as f64,
+            ScrollArea::Window => web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.document_element())
+                .map(|root| root.scroll_height() as f64)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_near_bottom(&self, threshold: LoadThreshold) -> bool {
+        match self {
+            ScrollArea::Element(element) => is_near_bottom(
+                element.scroll_top() as f64,
+                element.scroll_height() as f64,
+                element.offset_height() as f64,
+                threshold,
+            ),
+            ScrollArea::Window => {
+                let Some(window) = web_sys::window() else {
+                    return false;
+                };
+
+                let viewport_height = window
+                    .inner_height()
+                    .ok()
+                    .and_then(|height| height.as_f64())
+                    .unwrap_or_default();
+
+                is_near_bottom(
+                    window.scroll_y().unwrap_or_default(),
+                    self.scroll_height(),
+                    viewport_height,
+                    threshold,
+                )
+            }
+        }
+    }
+}
+
+/// Whether to load again straight after a load, because the new content still leaves the
+/// container within the threshold. A load that didn't change the height means the source is
+/// exhausted (or returned nothing), so asking again would never end
+fn should_keep_filling(
+    loads: u32,
+    height_before: f64,
+    height_after: f64,
+    near_bottom: bool,
+) -> bool {
+    near_bottom && height_after != height_before && loads < MAX_FILL_LOADS
+}
+
+/// Whether `scroll_top` is within `threshold` of the end
+fn is_near_bottom(
+    scroll_top: f64,
+    scroll_height: f64,
+    viewport_height: f64,
+    threshold: LoadThreshold,
+) -> bool {
+    let max_scroll = scroll_height - viewport_height;
+    scroll_top >= max_scroll - threshold.distance(max_scroll)
+}
+
+/// Allows one load at a time, plus the optional cooldown after each
+struct LoadGuard {
+    is_running: RefCell<bool>,
+    last_finished: Cell<f64>,
+    min_interval_ms: f64,
+}
+
+impl LoadGuard {
+    fn new(config: InfiniteScrollConfig) -> Rc<Self> {
+        Rc::new(Self {
+            is_running: RefCell::new(false),
+            last_finished: Cell::new(f64::NEG_INFINITY),
+            min_interval_ms: config.min_interval_ms as f64,
+        })
+    }
 
-                // Reset flag when done
-                *is_running.borrow_mut() = false;
-            });
+    fn is_ready(&self) -> bool {
+        if *self.is_running.borrow() {
+            return false;
         }
+
+        // Only read the clock when a cooldown is configured
+        self.min_interval_ms <= 0.0 || self.cooldown_elapsed(now())
+    }
+
+    fn cooldown_elapsed(&self, now: f64) -> bool {
+        now - self.last_finished.get() >= self.min_interval_ms
+    }
+
+    /// Sets the loading flag and runs `load`, clearing the flag once it completes. If the new
+    /// content doesn't fill `area` past `threshold` it loads again, since there's nothing left
+    /// for the user to scroll to trigger the next load
+    fn start<L, Fut>(self: &Rc<Self>, area: ScrollArea, threshold: LoadThreshold, load: L)
+    where
+        L: Fn() -> Fut + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        *self.is_running.borrow_mut() = true;
+
+        let guard = Rc::clone(self);
+        spawn_local(async move {
+            let mut loads = 0;
+            loop {
+                let height_before = area.scroll_height();
+
+                // Execute the async loading
+                load().await;
+                loads += 1;
+
+                // Give the new content a chance to render before measuring it
+                TimeoutFuture::new(0).await;
+
+                let keep_filling = should_keep_filling(
+                    loads,
+                    height_before,
+                    area.scroll_height(),
+                    area.is_near_bottom(threshold),
+                );
+                if !keep_filling {
+                    break;
+                }
+            }
+
+            // Reset flag when done
+            guard.last_finished.set(now());
+            *guard.is_running.borrow_mut() = false;
+        });
+    }
+}
+
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_near_bottom, should_keep_filling, InfiniteScrollConfig, LoadGuard, LoadThreshold,
+        MAX_FILL_LOADS,
+    };
+
+    #[test]
+    fn triggers_within_threshold_of_the_end() {
+        // 1000px of content in a 200px viewport leaves 800px to scroll, 10% is the last 80px
+        let threshold = LoadThreshold::Fraction(0.1);
+        assert!(!is_near_bottom(700.0, 1000.0, 200.0, threshold));
+        assert!(is_near_bottom(720.0, 1000.0, 200.0, threshold));
+        assert!(is_near_bottom(800.0, 1000.0, 200.0, threshold));
+    }
+
+    #[test]
+    fn pixel_threshold_is_independent_of_list_length() {
+        let threshold = LoadThreshold::Pixels(600.0);
+        // 100000px list: 10% would be 9980px early, 600px stays 600px
+        assert!(!is_near_bottom(99_000.0, 100_000.0, 200.0, threshold));
+        assert!(is_near_bottom(99_200.0, 100_000.0, 200.0, threshold));
+        assert!(!is_near_bottom(399.0, 1200.0, 200.0, threshold));
+        assert!(is_near_bottom(400.0, 1200.0, 200.0, threshold));
+    }
+
+    #[test]
+    fn unscrollable_content_is_always_near_bottom() {
+        assert!(is_near_bottom(0.0, 200.0, 400.0, LoadThreshold::default()));
+        assert!(is_near_bottom(
+            0.0,
+            200.0,
+            400.0,
+            LoadThreshold::Pixels(0.0)
+        ));
+    }
+
+    #[test]
+    fn keeps_filling_while_loads_grow_a_short_container() {
+        assert!(should_keep_filling(1, 400.0, 600.0, true));
+        assert!(
+            !should_keep_filling(1, 400.0, 600.0, false),
+            "now scrollable"
+        );
+    }
+
+    #[test]
+    fn stops_filling_when_a_load_adds_nothing() {
+        assert!(!should_keep_filling(1, 600.0, 600.0, true));
+        assert!(!should_keep_filling(MAX_FILL_LOADS, 400.0, 600.0, true));
+    }
+
+    #[test]
+    fn cooldown_blocks_triggers_until_it_elapses() {
+        let guard = LoadGuard::new(InfiniteScrollConfig {
+            min_interval_ms: 300,
+            ..InfiniteScrollConfig::default()
+        });
+        guard.last_finished.set(1000.0);
+
+        assert!(!guard.cooldown_elapsed(1200.0));
+        assert!(guard.cooldown_elapsed(1300.0));
+    }
+
+    #[test]
+    fn first_load_is_not_delayed_by_the_cooldown() {
+        let guard = LoadGuard::new(InfiniteScrollConfig {
+            min_interval_ms: 300,
+            ..InfiniteScrollConfig::default()
+        });
+
+        assert!(guard.cooldown_elapsed(0.0));
     }
 }
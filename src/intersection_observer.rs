@@ -1,17 +1,139 @@
-use std::{cell::RefCell, collections::HashMap, fmt::Debug, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
 
 use js_sys::Array;
+use leptos::{
+    html::ElementType,
+    prelude::{
+        request_animation_frame, set_timeout_with_handle, NodeRef, ReadSignal, RwSignal, Set,
+        TimeoutHandle,
+    },
+};
 use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+use web_sys::{
+    AddEventListenerOptions, Element, HtmlElement, IntersectionObserver, IntersectionObserverEntry,
+    IntersectionObserverInit,
+};
 
+use crate::cleanup::on_cleanup_local;
+
+/// Prefix of the attribute each `Intersectioner` stores its element ids under, followed by the
+/// instance's number so separate instances observing the same element don't clash
 const OBSERVER_ID_ATTRIBUTE: &'static str = "data-observer-id";
 
+static INTERSECTIONER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Shortest `delay` browsers accept alongside `trackVisibility`
+const MIN_VISIBILITY_DELAY_MS: f64 = 100.0;
+
 type HashIncrement = usize;
 
+type ObserverCallbacks = (HashIncrement, HashMap<HashIncrement, ObserverCallback>);
+
+/// Stores `observer_callback` under the next free id and returns the id
+fn insert_callback(
+    callbacks: &mut ObserverCallbacks,
+    observer_callback: ObserverCallback,
+) -> HashIncrement {
+    let id = callbacks.0;
+    callbacks.1.insert(id, observer_callback);
+    callbacks.0 = callbacks.0.wrapping_add(1);
+    id
+}
+
+fn observer_id(element: &Element, id_attribute: &str) -> Option<HashIncrement> {
+    element
+        .get_attribute(id_attribute)?
+        .parse::<HashIncrement>()
+        .ok()
+}
+
+type EntryCallback = Box<dyn Fn(&IntersectionObserverEntry) + 'static>;
+
 pub struct ObserverCallback {
     /// Only trigger once when element becomes visible (doesn't re-trigger on scroll out/in)
+    /// `Some(false)` until the first trigger, then `Some(true)`. `None` triggers every time.
+    /// The element stays observed after triggering unless `IntersectionConfig::unobserve_once`
     pub once: Option<bool>,
     pub callback: Box<dyn Fn() + 'static>,
+    /// Called when the element stops intersecting
+    pub on_exit: Option<Box<dyn Fn() + 'static>>,
+    /// Called with every entry reported for the element, intersecting or not
+    pub on_entry: Option<EntryCallback>,
+    /// Timer for the latest report while `IntersectionConfig::debounce_ms` waits for it to settle
+    pending: Option<TimeoutHandle>,
+    /// The observed element, kept so `Intersectioner::reconfigure` can observe it again
+    target: Option<Element>,
+    /// Completed the next time the element intersects, by `Intersectioner::visible_once`
+    /// waiting alongside the element's own callback
+    visible_waiters: Vec<Box<dyn FnOnce() + 'static>>,
+    /// Whether the element intersected as of its last settled report
+    is_intersecting: bool,
+    /// Unobserve once triggered whatever `IntersectionConfig::unobserve_once` says, for
+    /// registrations `visible_once` makes for itself
+    unobserve_once: bool,
+}
+
+impl ObserverCallback {
+    pub fn new<F>(once: bool, callback: F) -> Self
+    where
+        F: Fn() + 'static,
+    {
+        Self {
+            once: if once { Some(false) } else { None },
+            callback: Box::new(callback),
+            on_exit: None,
+            on_entry: None,
+            pending: None,
+            target: None,
+            visible_waiters: Vec::new(),
+            is_intersecting: false,
+            unobserve_once: false,
+        }
+    }
+
+    pub fn with_exit<F>(mut self, on_exit: F) -> Self
+    where
+        F: Fn() + 'static,
+    {
+        self.on_exit = Some(Box::new(on_exit));
+        self
+    }
+
+    pub fn with_entry<F>(mut self, on_entry: F) -> Self
+    where
+        F: Fn(&IntersectionObserverEntry) + 'static,
+    {
+        self.on_entry = Some(Box::new(on_entry));
+        self
+    }
+
+    /// Whether the callback should run for this intersection, marking once callbacks as triggered
+    fn should_trigger(&mut self) -> bool {
+        match self.once {
+            Some(true) => false,
+            Some(false) => {
+                self.once = Some(true);
+                true
+            }
+            None => true,
+        }
+    }
+
+    /// Whether the element can stop being observed, which is once a once callback has fired and
+    /// the observer was asked to unobserve it
+    fn is_finished(&self, unobserve_once: bool) -> bool {
+        self.once == Some(true) && (unobserve_once || self.unobserve_once)
+    }
 }
 
 /// Simple configuration for the intersection observer
@@ -20,72 +142,310 @@ pub struct IntersectionConfig {
     /// The threshold at which the observer's callback should be executed
     /// 0.0 = as soon as any part is visible, 1.0 = when fully visible
     pub threshold: f64,
+    /// Multiple thresholds to report at, used instead of `threshold` when not empty
+    pub thresholds: Vec<f64>,
+    /// Unobserve elements once their once callback fires, for observers that are done with an
+    /// element after it first appears. Otherwise they stay observed, so `on_entry`/`on_exit` keep
+    /// reporting and `Intersectioner::has_triggered` can be queried, e.g. for impression tracking
+    pub unobserve_once: bool,
+    /// Only run an element's enter/exit callbacks once its intersection state has held for this
+    /// long, coalescing the bursts fast scrolling produces. 0 runs them straight away.
+    /// `on_entry` still sees every report
+    pub debounce_ms: u32,
+    /// Only count an element as intersecting while the browser reports it actually visible, not
+    /// covered by other content or hidden by opacity, filters or transforms, e.g. for ad
+    /// impressions. Where IntersectionObserver v2 isn't supported it's plain intersection
+    pub track_visibility: bool,
+    /// Minimum ms between the browser's reports for an element. Raised to 100 while
+    /// `track_visibility` is on, the least browsers allow for it. Ignored without v2 support
+    pub delay: f64,
 }
 
 impl Default for IntersectionConfig {
     fn default() -> Self {
-        Self { threshold: 0.1 }
+        Self {
+            threshold: 0.1,
+            thresholds: Vec::new(),
+            unobserve_once: false,
+            debounce_ms: 0,
+            track_visibility: false,
+            delay: 0.0,
+        }
+    }
+}
+
+impl IntersectionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    pub fn unobserve_once(mut self, unobserve_once: bool) -> Self {
+        self.unobserve_once = unobserve_once;
+        self
+    }
+
+    pub fn debounce_ms(mut self, debounce_ms: u32) -> Self {
+        self.debounce_ms = debounce_ms;
+        self
+    }
+
+    pub fn track_visibility(mut self, track_visibility: bool) -> Self {
+        self.track_visibility = track_visibility;
+        self
+    }
+
+    pub fn delay(mut self, delay: f64) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// The `delay` actually passed to the browser
+    fn effective_delay(&self) -> f64 {
+        if self.track_visibility {
+            self.delay.max(MIN_VISIBILITY_DELAY_MS)
+        } else {
+            self.delay.max(0.0)
+        }
+    }
+
+    /// Reports at `steps + 1` evenly spaced thresholds from 0.0 to 1.0, for continuous ratios
+    /// with `Intersectioner::observe_ratio`. Every threshold is another crossing the browser
+    /// checks for each observed element, so prefer the coarsest granularity that looks smooth
+    /// (20 is usually plenty for an opacity fade, 100 gives 1% steps)
+    pub fn ratio_granularity(self, steps: usize) -> Self {
+        let steps = steps.max(1);
+        self.thresholds((0..=steps).map(|step| step as f64 / steps as f64).collect())
+    }
+
+    fn observer_options(&self) -> IntersectionObserverInit {
+        let options = IntersectionObserverInit::new();
+        if self.thresholds.is_empty() {
+            options.set_threshold(&JsValue::from_f64(self.threshold.clamp(0.0, 1.0)));
+        } else {
+            let thresholds = self
+                .thresholds
+                .iter()
+                .map(|threshold| JsValue::from_f64(threshold.clamp(0.0, 1.0)))
+                .collect::<Array>();
+            options.set_threshold(&thresholds);
+        }
+
+        // web-sys has no setters for the v2 options, and older browsers would ignore them anyway
+        if supports_visibility_tracking() {
+            let _ = js_sys::Reflect::set(
+                &options,
+                &"trackVisibility".into(),
+                &self.track_visibility.into(),
+            );
+            let _ = js_sys::Reflect::set(&options, &"delay".into(), &self.effective_delay().into());
+        }
+        options
+    }
+}
+
+/// Whether the browser implements IntersectionObserver v2's `trackVisibility`, detected by
+/// `isVisible` on `IntersectionObserverEntry`
+pub fn supports_visibility_tracking() -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &"IntersectionObserverEntry".into())
+        .and_then(|entry| js_sys::Reflect::get(&entry, &"prototype".into()))
+        .and_then(|prototype| js_sys::Reflect::has(&prototype, &"isVisible".into()))
+        .unwrap_or(false)
+}
+
+/// `entry.isVisible`, which web-sys doesn't bind. True where it isn't reported so unsupported
+/// browsers fall back to plain intersection
+fn entry_is_visible(entry: &IntersectionObserverEntry) -> bool {
+    js_sys::Reflect::get(entry, &"isVisible".into())
+        .ok()
+        .and_then(|visible| visible.as_bool())
+        .unwrap_or(true)
+}
+
+/// Shared between `VisibleOnce` and the callback that completes it
+#[derive(Default)]
+struct OnceState {
+    fired: bool,
+    waker: Option<Waker>,
+}
+
+impl OnceState {
+    fn fire(&mut self) {
+        self.fired = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by `Intersectioner::visible_once`
+pub struct VisibleOnce {
+    state: Rc<RefCell<OnceState>>,
+}
+
+impl Future for VisibleOnce {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.borrow_mut();
+        if state.fired {
+            return Poll::Ready(());
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Runs `id`'s enter or exit callback for a settled intersection state, unobserving the element
+/// once it's finished
+fn settle(
+    callbacks: &mut ObserverCallbacks,
+    id: HashIncrement,
+    is_intersecting: bool,
+    target: &Element,
+    observer: &IntersectionObserver,
+    unobserve_once: bool,
+) {
+    let Some(observee) = callbacks.1.get_mut(&id) else {
+        return;
+    };
+    observee.is_intersecting = is_intersecting;
+
+    if is_intersecting {
+        for waiter in observee.visible_waiters.drain(..) {
+            waiter();
+        }
+
+        // Check if we should only trigger once
+        if !observee.should_trigger() {
+            return;
+        }
+
+        (observee.callback)();
+
+        if observee.is_finished(unobserve_once) {
+            callbacks.1.remove(&id);
+            observer.unobserve(target);
+        }
+    } else if let Some(on_exit) = &observee.on_exit {
+        on_exit();
     }
 }
 
+/// One `IntersectionObserver` shared by any number of elements, each with its own callback and
+/// once state. Prefer one per threshold set over one per element, e.g. a single
+/// `ratio_granularity` observer for every image in a grid
 #[derive(Clone)]
 pub struct Intersectioner {
-    observer: IntersectionObserver,
-    observer_callbacks:
-        Rc<std::cell::RefCell<(HashIncrement, HashMap<HashIncrement, ObserverCallback>)>>,
-    _observer_event_callback: Rc<Closure<dyn FnMut(Array)>>,
+    observer: Rc<RefCell<IntersectionObserver>>,
+    /// Attribute this instance's element ids live under
+    id_attribute: Rc<str>,
+    config: Rc<RefCell<IntersectionConfig>>,
+    observer_callbacks: Rc<RefCell<ObserverCallbacks>>,
+    observer_event_callback: Rc<Closure<dyn FnMut(Array, IntersectionObserver)>>,
 }
 
 impl Intersectioner {
     pub fn new(config: IntersectionConfig) -> Self {
-        let observer_callbacks = Rc::new(RefCell::new((0, HashMap::new())));
+        let observer_callbacks: Rc<RefCell<ObserverCallbacks>> =
+            Rc::new(RefCell::new((0, HashMap::new())));
 
         let inner_callbacks = observer_callbacks.clone();
+        let config = Rc::new(RefCell::new(config));
+        let inner_config = config.clone();
+        let instance = INTERSECTIONER_COUNT.fetch_add(1, Ordering::Relaxed);
+        let id_attribute: Rc<str> = Rc::from(format!("{OBSERVER_ID_ATTRIBUTE}-{instance}"));
+        let inner_id_attribute = id_attribute.clone();
+
+        let on_entries = move |entries: js_sys::Array, observer: IntersectionObserver| {
+            let (unobserve_once, debounce, track_visibility) = {
+                let config = inner_config.borrow();
+                let debounce = Duration::from_millis(config.debounce_ms as u64);
+                (config.unobserve_once, debounce, config.track_visibility)
+            };
 
-        let callback = Closure::wrap(Box::new(move |entries: js_sys::Array| {
             for i in 0..entries.length() {
                 let entry = entries.get(i).unchecked_into::<IntersectionObserverEntry>();
 
-                if entry.is_intersecting() {
-                    // We love to unwrap unwrap unwrap (if this became serious, we'd need to handle this)
-                    let mut map = inner_callbacks.borrow_mut();
-
-                    let observee: &mut ObserverCallback = map
-                        .1
-                        .get_mut(
-                            &entry
-                                .target()
-                                .get_attribute(OBSERVER_ID_ATTRIBUTE)
-                                .unwrap()
-                                .parse::<usize>()
-                                .unwrap(),
-                        )
-                        .unwrap();
-
-                    // Check if we should only trigger once
-                    if let Some(triggered) = observee.once {
-                        if triggered {
-                            return;
-                        }
-                        observee.once = Some(true);
-                    }
+                let Some(id) = observer_id(&entry.target(), &inner_id_attribute) else {
+                    continue;
+                };
+                let mut map = inner_callbacks.borrow_mut();
+                let Some(observee) = map.1.get_mut(&id) else {
+                    continue;
+                };
 
-                    (observee.callback)();
+                if let Some(on_entry) = &observee.on_entry {
+                    on_entry(&entry);
                 }
-            }
-        }) as Box<dyn FnMut(js_sys::Array)>);
 
-        let options = IntersectionObserverInit::new();
-        options.set_threshold(&JsValue::from_f64(config.threshold.clamp(0.0, 1.0)));
+                let is_intersecting =
+                    entry.is_intersecting() && (!track_visibility || entry_is_visible(&entry));
+                if debounce.is_zero() {
+                    settle(
+                        &mut map,
+                        id,
+                        is_intersecting,
+                        &entry.target(),
+                        &observer,
+                        unobserve_once,
+                    );
+                    continue;
+                }
 
-        let observer =
-            IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options)
-                .unwrap();
+                // Only the latest report for the element survives the wait
+                if let Some(pending) = observee.pending.take() {
+                    pending.clear();
+                }
+                let settled_callbacks = inner_callbacks.clone();
+                let target = entry.target();
+                let observer = observer.clone();
+                observee.pending = set_timeout_with_handle(
+                    move || {
+                        let mut map = settled_callbacks.borrow_mut();
+                        if let Some(observee) = map.1.get_mut(&id) {
+                            observee.pending = None;
+                            settle(
+                                &mut map,
+                                id,
+                                is_intersecting,
+                                &target,
+                                &observer,
+                                unobserve_once,
+                            );
+                        }
+                    },
+                    debounce,
+                )
+                .ok();
+            }
+        };
+        let callback = Closure::wrap(
+            Box::new(on_entries) as Box<dyn FnMut(js_sys::Array, IntersectionObserver)>
+        );
+
+        let observer = IntersectionObserver::new_with_options(
+            callback.as_ref().unchecked_ref(),
+            &config.borrow().observer_options(),
+        )
+        .unwrap();
 
         Intersectioner {
-            observer,
+            observer: Rc::new(RefCell::new(observer)),
+            id_attribute,
+            config,
             observer_callbacks,
-            _observer_event_callback: Rc::new(callback),
+            observer_event_callback: Rc::new(callback),
         }
     }
 
@@ -93,36 +453,488 @@ impl Intersectioner {
     where
         F: Fn() + 'static,
     {
+        self.register(element, ObserverCallback::new(once, callback));
+    }
+
+    /// Resolves the first time `element` intersects, e.g. to start a fetch once its loading
+    /// placeholder is on screen inside `spawn_local`. An element this observer already tracks
+    /// keeps its callback, the future waits alongside it and resolves straight away if the
+    /// element is already in view. Never resolves if the element is unobserved first
+    pub fn visible_once(&self, element: &Element) -> VisibleOnce {
+        let state = Rc::new(RefCell::new(OnceState::default()));
+        let completes = state.clone();
+        let fire = move || completes.borrow_mut().fire();
+
+        {
+            let mut callbacks = self.observer_callbacks.borrow_mut();
+            let tracked =
+                observer_id(element, &self.id_attribute).and_then(|id| callbacks.1.get_mut(&id));
+            if let Some(observee) = tracked {
+                if observee.is_intersecting {
+                    fire();
+                } else {
+                    observee.visible_waiters.push(Box::new(fire));
+                }
+                return VisibleOnce { state };
+            }
+        }
+
+        // Nothing else needs the element, so it's unobserved once the future resolves
+        let mut observer_callback = ObserverCallback::new(true, fire);
+        observer_callback.unobserve_once = true;
+        self.register(element, observer_callback);
+
+        VisibleOnce { state }
+    }
+
+    /// `observe` for an element that may not be mounted yet, e.g. a `NodeRef` read while the
+    /// component is still being built. Observing starts once the node loads and stops when the
+    /// calling component unmounts
+    pub fn observe_node_ref<E, F>(&self, node_ref: NodeRef<E>, once: bool, callback: F)
+    where
+        E: ElementType,
+        E::Output: JsCast + Clone + 'static,
+        F: Fn() + 'static,
+    {
+        let observed = Rc::new(RefCell::new(None::<Element>));
+
+        node_ref.on_load({
+            let observer = self.clone();
+            let observed = observed.clone();
+            move |element| {
+                let element = element.unchecked_into::<Element>();
+                observer.observe(&element, once, callback);
+                *observed.borrow_mut() = Some(element);
+            }
+        });
+
+        let observer = self.clone();
+        on_cleanup_local(move || {
+            if let Some(element) = observed.borrow_mut().take() {
+                observer.unobserve(&element);
+            }
+        });
+    }
+
+    /// Observes `element`, calling `on_enter` each time it intersects and `on_exit` each time it
+    /// stops intersecting. `on_exit` also runs on the observer's initial report if not visible
+    pub fn observe_with_exit<F, G>(&self, element: &Element, on_enter: F, on_exit: G)
+    where
+        F: Fn() + 'static,
+        G: Fn() + 'static,
+    {
+        self.register(
+            element,
+            ObserverCallback::new(false, on_enter).with_exit(on_exit),
+        );
+    }
+
+    /// Calls `callback` with the element's visible ratio (0.0-1.0) every time the observer
+    /// reports it. Ratios only update at the observer's thresholds, so create the observer with
+    /// `IntersectionConfig::ratio_granularity` for a continuous value
+    pub fn observe_ratio<F>(&self, element: &Element, callback: F)
+    where
+        F: Fn(f64) + 'static,
+    {
+        self.register(
+            element,
+            ObserverCallback::new(false, || {}).with_entry(move |entry| {
+                callback(if entry.is_intersecting() {
+                    entry.intersection_ratio()
+                } else {
+                    0.0
+                })
+            }),
+        );
+    }
+
+    /// Reveals `elements` in a cascade, giving each a `transition-delay` of `step_ms` times its
+    /// position among the elements revealed in the same observer report before calling
+    /// `on_reveal` with its index. Elements already in view on mount cascade from mount, and the
+    /// delay is removed after the first transition so later transitions aren't delayed
+    pub fn observe_staggered<F>(&self, elements: &[HtmlElement], step_ms: u32, on_reveal: F)
+    where
+        F: Fn(usize) + 'static,
+    {
+        let on_reveal = Rc::new(on_reveal);
+        let batch_position = Rc::new(Cell::new(0u32));
+
+        for (index, element) in elements.iter().enumerate() {
+            let on_reveal = on_reveal.clone();
+            let batch_position = batch_position.clone();
+            let target = element.clone();
+
+            self.observe(element, true, move || {
+                let position = batch_position.get();
+                if position == 0 {
+                    // Everything reported before the next frame belongs to the same cascade
+                    let batch_position = batch_position.clone();
+                    request_animation_frame(move || batch_position.set(0));
+                }
+                batch_position.set(position + 1);
+
+                let style = target.style();
+                let _ =
+                    style.set_property("transition-delay", &format!("{}ms", position * step_ms));
+
+                let delayed = target.clone();
+                let clear_delay = Closure::once_into_js(move || {
+                    let _ = delayed.style().remove_property("transition-delay");
+                });
+                let options = AddEventListenerOptions::new();
+                options.set_once(true);
+                let _ = target.add_event_listener_with_callback_and_add_event_listener_options(
+                    "transitionend",
+                    clear_delay.unchecked_ref(),
+                    &options,
+                );
+
+                on_reveal(index);
+            });
+        }
+    }
+
+    pub(crate) fn register(&self, element: &Element, mut observer_callback: ObserverCallback) {
         let mut callbacks = self.observer_callbacks.borrow_mut();
 
-        let observer_callback = ObserverCallback {
-            once: if once { Some(true) } else { None },
-            callback: Box::new(callback),
-        };
+        // Re-observing replaces this instance's old registration, so a once flag that already
+        // fired doesn't carry over to the new callback. Other instances keep their own
+        if let Some(previous) = observer_id(element, &self.id_attribute) {
+            callbacks.1.remove(&previous);
+        }
 
-        let index = callbacks.0;
-        callbacks.1.insert(index, observer_callback);
+        observer_callback.target = Some(element.clone());
+        let id = insert_callback(&mut callbacks, observer_callback);
+        _ = element.set_attribute(&self.id_attribute, &id.to_string());
 
-        _ = element.set_attribute(OBSERVER_ID_ATTRIBUTE, &callbacks.0.to_string());
+        self.observer.borrow().observe(element.unchecked_ref());
+    }
+
+    /// Swaps in an observer built from `config`, e.g. a different threshold on mobile, and
+    /// observes every tracked element again with its callback and once state intact. Like any
+    /// new observer it reports each element straight away, so repeating callbacks run again for
+    /// elements in view and `on_exit` runs for those that aren't
+    pub fn reconfigure(&self, config: IntersectionConfig) {
+        let observer = IntersectionObserver::new_with_options(
+            (*self.observer_event_callback).as_ref().unchecked_ref(),
+            &config.observer_options(),
+        )
+        .unwrap();
+        self.observer.replace(observer).disconnect();
+        *self.config.borrow_mut() = config;
 
-        callbacks.0 += callbacks.0.wrapping_add(1);
-        self.observer.observe(element.unchecked_ref());
+        let observer = self.observer.borrow();
+        for observee in self.observer_callbacks.borrow_mut().1.values_mut() {
+            // The new observer's first report supersedes anything still settling
+            if let Some(pending) = observee.pending.take() {
+                pending.clear();
+            }
+            if let Some(target) = &observee.target {
+                observer.observe(target);
+            }
+        }
     }
 
     pub fn unobserve(&self, element: &Element) {
-        let id = element
-            .get_attribute(OBSERVER_ID_ATTRIBUTE)
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
+        if let Some(id) = observer_id(element, &self.id_attribute) {
+            self.observer_callbacks.borrow_mut().1.remove(&id);
+        }
+        self.observer.borrow().unobserve(element.unchecked_ref());
+    }
 
-        self.observer_callbacks.borrow_mut().1.remove(&id);
-        self.observer.unobserve(element.unchecked_ref());
+    /// Whether `element`'s once callback has fired. Only stays true while the element is
+    /// observed, so always false with `IntersectionConfig::unobserve_once`
+    pub fn has_triggered(&self, element: &Element) -> bool {
+        observer_id(element, &self.id_attribute).is_some_and(|id| {
+            self.observer_callbacks
+                .borrow()
+                .1
+                .get(&id)
+                .is_some_and(|observee| observee.once == Some(true))
+        })
+    }
+
+    /// Number of elements registered and not yet unobserved. A count that keeps growing as
+    /// elements leave the DOM points at a missing `unobserve`
+    pub fn observed_count(&self) -> usize {
+        self.observer_callbacks.borrow().1.len()
     }
 }
 
 impl Drop for Intersectioner {
     fn drop(&mut self) {
-        self.observer.disconnect();
+        // Clones share the observer, so only the last one to go disconnects it
+        if Rc::strong_count(&self.observer) == 1 {
+            self.observer.borrow().disconnect();
+        }
+    }
+}
+
+/// Reactive flag for whether `node_ref`'s element is in the viewport, e.g. to only mount a heavy
+/// chart once it's visible. The element is unobserved when the calling component unmounts
+pub fn use_in_viewport<E>(node_ref: NodeRef<E>, config: IntersectionConfig) -> ReadSignal<bool>
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let in_viewport = RwSignal::new(false);
+    let observer = Rc::new(Intersectioner::new(config));
+    let observed = Rc::new(RefCell::new(None::<Element>));
+
+    node_ref.on_load({
+        let observer = observer.clone();
+        let observed = observed.clone();
+        move |element| {
+            let element = element.unchecked_into::<Element>();
+            observer.observe_with_exit(
+                &element,
+                move || in_viewport.set(true),
+                move || in_viewport.set(false),
+            );
+            *observed.borrow_mut() = Some(element);
+        }
+    });
+
+    on_cleanup_local(move || {
+        if let Some(element) = observed.borrow_mut().take() {
+            observer.unobserve(&element);
+        }
+    });
+
+    in_viewport.read_only()
+}
+
+/// What `sticky_until_exit` has seen of its sentinel and section
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+struct StickyState {
+    sentinel_above: bool,
+    section_visible: bool,
+}
+
+impl StickyState {
+    /// Pinned once the spot `target` started at has scrolled off the top, for as long as some of
+    /// its section is still on screen
+    fn is_pinned(self) -> bool {
+        self.sentinel_above && self.section_visible
+    }
+}
+
+struct Sticky {
+    target: HtmlElement,
+    sentinel: HtmlElement,
+    state: Cell<StickyState>,
+    pinned: Cell<bool>,
+}
+
+impl Sticky {
+    fn update(&self, change: impl FnOnce(&mut StickyState)) {
+        let mut state = self.state.get();
+        change(&mut state);
+        self.state.set(state);
+
+        let pin = state.is_pinned();
+        if pin == self.pinned.replace(pin) {
+            return;
+        }
+
+        let target_style = self.target.style();
+        let sentinel_style = self.sentinel.style();
+        if pin {
+            // Fixed takes it out of flow, so keep its size and hold its space with the sentinel
+            let rect = self.target.get_bounding_client_rect();
+            let _ = sentinel_style.set_property("height", &format!("{}px", rect.height()));
+            let _ = target_style.set_property("position", "fixed");
+            let _ = target_style.set_property("top", "0px");
+            let _ = target_style.set_property("left", &format!("{}px", rect.left()));
+            let _ = target_style.set_property("width", &format!("{}px", rect.width()));
+        } else {
+            let _ = sentinel_style.remove_property("height");
+            for property in ["position", "top", "left", "width"] {
+                let _ = target_style.remove_property(property);
+            }
+        }
+    }
+}
+
+/// Pins `target` with `position: fixed` once it would scroll off the top of the viewport and
+/// releases it when `section` (usually its parent) leaves the viewport. An empty sentinel is
+/// inserted before `target` to detect the first and hold its space while pinned. Everything is
+/// undone when the calling component unmounts
+pub fn sticky_until_exit(target: &HtmlElement, section: &Element) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document"))?;
+    let sentinel = document
+        .create_element("div")?
+        .unchecked_into::<HtmlElement>();
+    sentinel.set_attribute("aria-hidden", "true")?;
+    target.before_with_node_1(&sentinel)?;
+
+    let sticky = Rc::new(Sticky {
+        target: target.clone(),
+        sentinel: sentinel.clone(),
+        state: Cell::new(StickyState::default()),
+        pinned: Cell::new(false),
+    });
+    let observer = Intersectioner::new(IntersectionConfig::new().threshold(0.0));
+
+    let sentinel_sticky = sticky.clone();
+    observer.register(
+        &sentinel,
+        ObserverCallback::new(false, || {}).with_entry(move |entry| {
+            // Out of view above the viewport, rather than below it
+            let above = !entry.is_intersecting() && entry.bounding_client_rect().top() < 0.0;
+            sentinel_sticky.update(|state| state.sentinel_above = above);
+        }),
+    );
+
+    let section_sticky = sticky.clone();
+    observer.register(
+        section,
+        ObserverCallback::new(false, || {}).with_entry(move |entry| {
+            let visible = entry.is_intersecting();
+            section_sticky.update(|state| state.section_visible = visible);
+        }),
+    );
+
+    on_cleanup_local(move || {
+        observer.observer.borrow().disconnect();
+        sticky.update(|state| *state = StickyState::default());
+        sticky.sentinel.remove();
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+        future::Future,
+        pin::pin,
+        rc::Rc,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::{
+        insert_callback, IntersectionConfig, ObserverCallback, OnceState, StickyState, VisibleOnce,
+    };
+
+    #[test]
+    fn once_callback_triggers_exactly_once() {
+        let mut observee = ObserverCallback::new(true, || {});
+
+        assert!(observee.should_trigger(), "first intersect should fire");
+        assert!(!observee.should_trigger());
+        assert!(!observee.should_trigger());
+    }
+
+    #[test]
+    fn once_callback_only_unobserves_when_asked() {
+        let mut observee = ObserverCallback::new(true, || {});
+        assert!(!observee.is_finished(true), "not finished before it fires");
+
+        observee.should_trigger();
+        assert!(observee.is_finished(true));
+        assert!(!observee.is_finished(false));
+    }
+
+    #[test]
+    fn visible_once_registration_unobserves_without_the_config() {
+        let mut observee = ObserverCallback::new(true, || {});
+        observee.unobserve_once = true;
+        observee.should_trigger();
+
+        assert!(observee.is_finished(false));
+    }
+
+    #[test]
+    fn repeating_callback_is_never_finished() {
+        let mut observee = ObserverCallback::new(false, || {});
+        observee.should_trigger();
+
+        assert!(!observee.is_finished(true));
+    }
+
+    #[test]
+    fn one_observer_keeps_a_hundred_elements_independent() {
+        let mut callbacks = (0, HashMap::new());
+        let ids = (0..100)
+            .map(|_| insert_callback(&mut callbacks, ObserverCallback::new(true, || {})))
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, (0..100).collect::<Vec<_>>());
+        assert_eq!(callbacks.1.len(), 100);
+
+        // Triggering one element's once callback leaves every other element untouched
+        assert!(callbacks.1.get_mut(&42).unwrap().should_trigger());
+        assert!(!callbacks.1.get_mut(&42).unwrap().should_trigger());
+        assert!(callbacks
+            .1
+            .iter()
+            .filter(|(id, _)| **id != 42)
+            .all(|(_, observee)| observee.once == Some(false)));
+    }
+
+    #[test]
+    fn visible_once_resolves_after_the_callback_fires() {
+        let state = Rc::new(RefCell::new(OnceState::default()));
+        let mut future = pin!(VisibleOnce {
+            state: state.clone()
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert!(state.borrow().waker.is_some());
+
+        state.borrow_mut().fire();
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn repeating_callback_triggers_every_time() {
+        let mut observee = ObserverCallback::new(false, || {});
+
+        assert!(observee.should_trigger());
+        assert!(observee.should_trigger());
+    }
+
+    #[test]
+    fn sticky_pins_only_between_sentinel_leaving_and_section_exiting() {
+        let mut state = StickyState {
+            sentinel_above: false,
+            section_visible: true,
+        };
+        assert!(!state.is_pinned());
+
+        state.sentinel_above = true;
+        assert!(state.is_pinned());
+
+        state.section_visible = false;
+        assert!(!state.is_pinned());
+    }
+
+    #[test]
+    fn ratio_granularity_spans_zero_to_one() {
+        let config = IntersectionConfig::new().ratio_granularity(4);
+        assert_eq!(config.thresholds, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+
+        let dense = IntersectionConfig::new().ratio_granularity(100);
+        assert_eq!(dense.thresholds.len(), 101);
+        assert_eq!(dense.thresholds.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn visibility_tracking_raises_delay_to_the_minimum() {
+        assert_eq!(IntersectionConfig::new().effective_delay(), 0.0);
+        assert_eq!(
+            IntersectionConfig::new().delay(50.0).effective_delay(),
+            50.0
+        );
+
+        let tracked = IntersectionConfig::new().track_visibility(true);
+        assert_eq!(tracked.effective_delay(), 100.0);
+        assert_eq!(tracked.delay(250.0).effective_delay(), 250.0);
     }
 }
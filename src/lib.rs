@@ -1,3 +1,5 @@
+pub mod a11y;
+pub mod cleanup;
 pub mod cookies;
 #[cfg(feature = "components")]
 pub mod components;
@@ -5,10 +7,16 @@ pub mod extensions;
 #[cfg(feature = "components")]
 pub mod infinite_scroll;
 pub mod intersection_observer;
+pub mod media;
 pub mod mock;
 pub mod net;
 pub mod router_utils;
 pub mod scroll_examples;
+pub mod scroll_lock;
+pub mod scroll_spy;
 pub mod scroll_storyteller;
 pub mod scroll_test;
+pub mod storage;
+pub mod swipe;
+pub mod timing;
 pub mod wrappers;
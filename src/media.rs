@@ -0,0 +1,113 @@
+use leptos::ev::resize;
+use leptos::prelude::{on_cleanup, window_event_listener, ReadSignal, RwSignal, Set};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::MediaQueryList;
+
+use crate::cleanup::on_cleanup_local;
+use crate::timing::debounce;
+
+/// How long `use_window_size` waits for resizing to stop, so dragging a window edge updates once
+const WINDOW_SIZE_DEBOUNCE_MS: u32 = 100;
+
+/// The OS light/dark preference from `prefers-color-scheme`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    fn from_prefers_dark(prefers_dark: bool) -> Self {
+        if prefers_dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        }
+    }
+}
+
+fn media_query_list(query: &str) -> Option<MediaQueryList> {
+    web_sys::window()?.match_media(query).ok().flatten()
+}
+
+/// Whether `query` currently matches, false where `matchMedia` is unavailable
+pub fn matches_media(query: &str) -> bool {
+    media_query_list(query).is_some_and(|list| list.matches())
+}
+
+/// Reactive flag for whether the CSS media `query` matches, updated as it starts or stops
+/// matching. The listener is removed when the calling component unmounts
+pub fn use_media_query(query: &str) -> ReadSignal<bool> {
+    let matches = RwSignal::new(false);
+    watch_media_query(query, move |is_match| matches.set(is_match));
+    matches.read_only()
+}
+
+/// Reactive OS color scheme preference, following changes made while the page is open. Light
+/// where the preference can't be read
+pub fn use_color_scheme() -> ReadSignal<ColorScheme> {
+    let color_scheme = RwSignal::new(ColorScheme::default());
+    watch_media_query("(prefers-color-scheme: dark)", move |prefers_dark| {
+        color_scheme.set(ColorScheme::from_prefers_dark(prefers_dark))
+    });
+    color_scheme.read_only()
+}
+
+/// Calls `on_change` with whether `query` matches now and again each time that changes, until the
+/// calling component unmounts
+fn watch_media_query<F>(query: &str, on_change: F)
+where
+    F: Fn(bool) + 'static,
+{
+    let Some(list) = media_query_list(query) else {
+        return;
+    };
+    on_change(list.matches());
+
+    let watched = list.clone();
+    let on_media_change = Closure::wrap(Box::new(move || {
+        on_change(watched.matches());
+    }) as Box<dyn FnMut()>);
+
+    if list
+        .add_event_listener_with_callback("change", on_media_change.as_ref().unchecked_ref())
+        .is_err()
+    {
+        return;
+    }
+
+    on_cleanup_local(move || {
+        let _ = list.remove_event_listener_with_callback(
+            "change",
+            on_media_change.as_ref().unchecked_ref(),
+        );
+    });
+}
+
+/// Reactive `(inner_width, inner_height)` of the window in px, updated once resizing settles.
+/// `(0.0, 0.0)` without a window. The listener is removed when the calling component unmounts
+pub fn use_window_size() -> ReadSignal<(f64, f64)> {
+    let size = RwSignal::new(window_size().unwrap_or_default());
+
+    let handle = window_event_listener(
+        resize,
+        debounce(WINDOW_SIZE_DEBOUNCE_MS, move |_| {
+            if let Some(current) = window_size() {
+                size.set(current);
+            }
+        }),
+    );
+    on_cleanup(move || handle.remove());
+
+    size.read_only()
+}
+
+fn window_size() -> Option<(f64, f64)> {
+    let window = web_sys::window()?;
+    Some((
+        window.inner_width().ok()?.as_f64()?,
+        window.inner_height().ok()?.as_f64()?,
+    ))
+}
@@ -1,10 +1,319 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use anyhow::Result;
 use gloo_net::http::{Request, RequestBuilder, Response};
 
-use serde_lite::{Deserialize, Serialize};
+use serde_lite::{Deserialize, Intermediate, Serialize};
+use web_sys::{AbortController, AbortSignal};
 
 use super::cookies::get_cookie;
 
+static NET_LOGGING: AtomicBool = AtomicBool::new(false);
+
+/// Source of the token `authenticate()` sends
+pub trait TokenProvider {
+    fn current_token(&self) -> Option<String>;
+}
+
+/// Reads the token from a cookie, `auth-token` by default. This is the provider used unless
+/// another is set with `set_token_provider`
+#[derive(Clone, Debug)]
+pub struct CookieTokenProvider {
+    pub cookie_name: String,
+}
+
+impl Default for CookieTokenProvider {
+    fn default() -> Self {
+        Self {
+            cookie_name: "auth-token".to_string(),
+        }
+    }
+}
+
+impl TokenProvider for CookieTokenProvider {
+    fn current_token(&self) -> Option<String> {
+        get_cookie(&self.cookie_name)
+    }
+}
+
+/// Holds the token in memory only so it's never written to a cookie. Clones share the token
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTokenProvider {
+    token: Rc<RefCell<Option<String>>>,
+}
+
+impl MemoryTokenProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_token<S: Into<String>>(&self, token: S) {
+        *self.token.borrow_mut() = Some(token.into());
+    }
+
+    pub fn clear(&self) {
+        *self.token.borrow_mut() = None;
+    }
+}
+
+impl TokenProvider for MemoryTokenProvider {
+    fn current_token(&self) -> Option<String> {
+        self.token.borrow().clone()
+    }
+}
+
+/// How `authenticate()` sends the token. The cookie it's read from is set through
+/// `CookieTokenProvider`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthConfig {
+    /// `Authorization` by default
+    pub header_name: String,
+    /// Prepended to the token as is, so include any separating space. `"Bearer "` by default,
+    /// e.g. `"Token "`, or empty to send the bare token
+    pub scheme: String,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            header_name: "Authorization".to_string(),
+            scheme: "Bearer ".to_string(),
+        }
+    }
+}
+
+impl AuthConfig {
+    fn header_value(&self, token: &str) -> String {
+        format!("{}{}", self.scheme, token)
+    }
+}
+
+type TokenRefresh = Rc<dyn Fn() -> Pin<Box<dyn Future<Output = bool>>>>;
+type RefreshPredicate = Rc<dyn Fn(&Response) -> bool>;
+
+thread_local! {
+    static TOKEN_PROVIDER: RefCell<Rc<dyn TokenProvider>> =
+        RefCell::new(Rc::new(CookieTokenProvider::default()));
+    static AUTH_CONFIG: RefCell<AuthConfig> = RefCell::new(AuthConfig::default());
+    static TOKEN_REFRESH: RefCell<Option<TokenRefresh>> = const { RefCell::new(None) };
+    static REFRESH_PREDICATE: RefCell<Option<RefreshPredicate>> = const { RefCell::new(None) };
+}
+
+/// Replaces the header name and scheme `authenticate()` uses
+pub fn set_auth_config(config: AuthConfig) {
+    AUTH_CONFIG.with(|current| *current.borrow_mut() = config);
+}
+
+fn auth_config() -> AuthConfig {
+    AUTH_CONFIG.with(|current| current.borrow().clone())
+}
+
+/// Replaces the provider `authenticate()` reads the token from
+pub fn set_token_provider<P: TokenProvider + 'static>(provider: P) {
+    TOKEN_PROVIDER.with(|current| *current.borrow_mut() = Rc::new(provider));
+}
+
+/// Runs `refresh` when an authenticated request gets a 401, then retries the request once with
+/// the provider's new token. `refresh` resolves to whether it got a new token; on false the 401
+/// is returned as is. Pair with `set_refresh_predicate` when some 401s aren't an expired token
+pub fn set_token_refresh<F, Fut>(refresh: F)
+where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = bool> + 'static,
+{
+    let refresh: TokenRefresh = Rc::new(move || Box::pin(refresh()));
+    TOKEN_REFRESH.with(|current| *current.borrow_mut() = Some(refresh));
+}
+
+/// Decides from a 401 response whether refreshing the token could help, so endpoints that are
+/// simply forbidden fail fast instead of refreshing. Every 401 is refreshed by default, e.g. use
+/// `set_refresh_predicate(is_invalid_token)` for servers that follow RFC 6750
+pub fn set_refresh_predicate<P>(predicate: P)
+where
+    P: Fn(&Response) -> bool + 'static,
+{
+    let predicate: RefreshPredicate = Rc::new(predicate);
+    REFRESH_PREDICATE.with(|current| *current.borrow_mut() = Some(predicate));
+}
+
+/// Whether the 401's `WWW-Authenticate` header reports `error="invalid_token"`, which covers
+/// expired tokens, as opposed to a valid token without access
+pub fn is_invalid_token(response: &Response) -> bool {
+    response
+        .headers()
+        .get("WWW-Authenticate")
+        .is_some_and(|challenge| reports_invalid_token(&challenge))
+}
+
+fn reports_invalid_token(challenge: &str) -> bool {
+    challenge
+        .split(',')
+        .filter_map(|param| param.split_once('='))
+        .any(|(name, value)| {
+            name.trim().ends_with("error") && value.trim().trim_matches('"') == "invalid_token"
+        })
+}
+
+fn current_token() -> Option<String> {
+    TOKEN_PROVIDER
+        .with(|current| current.borrow().clone())
+        .current_token()
+}
+
+/// Logs every request sent through `NetResponsive` and its response status and timing to the
+/// console. `Authorization` and `AuthConfig` header values are redacted
+pub fn set_net_logging(enabled: bool) {
+    NET_LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+fn now() -> Option<f64> {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+}
+
+async fn send(request: Request) -> Result<Response> {
+    if !NET_LOGGING.load(Ordering::Relaxed) {
+        return Ok(request.send().await?);
+    }
+
+    let method = request.method().to_string();
+    let url = request.url();
+    let auth_header = auth_config().header_name;
+    let headers = request
+        .headers()
+        .entries()
+        .map(|(name, value)| {
+            if name.eq_ignore_ascii_case("authorization") || name.eq_ignore_ascii_case(&auth_header)
+            {
+                format!("{}: [redacted]", name)
+            } else {
+                format!("{}: {}", name, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    web_sys::console::log_1(&format!("[net] {} {} ({})", method, url, headers).into());
+
+    let started = now();
+    let result = request.send().await;
+    let elapsed = now().zip(started).map(|(end, start)| end - start);
+
+    match &result {
+        Ok(response) => web_sys::console::log_1(
+            &format!(
+                "[net] {} {} -> {} in {:.1}ms",
+                method,
+                url,
+                response.status(),
+                elapsed.unwrap_or_default()
+            )
+            .into(),
+        ),
+        Err(error) => web_sys::console::error_1(
+            &format!(
+                "[net] {} {} failed in {:.1}ms: {}",
+                method,
+                url,
+                elapsed.unwrap_or_default(),
+                error
+            )
+            .into(),
+        ),
+    }
+
+    Ok(result?)
+}
+
+/// Error for a request aborted through its `CancelToken`. It's expected whenever a newer request
+/// replaces an older one, so check for it with `error.is::<Canceled>()` and ignore it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Canceled;
+
+impl fmt::Display for Canceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Request canceled")
+    }
+}
+
+impl std::error::Error for Canceled {}
+
+/// Aborts the previous request sent with it whenever a new one starts, e.g. one token per search
+/// box so a slow response for an old query can't land after a newer one. Clones share the token
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    controller: Rc<RefCell<Option<AbortController>>>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aborts the in-flight request, if any
+    pub fn cancel(&self) {
+        if let Some(controller) = self.controller.borrow_mut().take() {
+            controller.abort();
+        }
+    }
+
+    /// Cancels the previous request and returns the signal for the next one
+    fn next_signal(&self) -> Option<AbortSignal> {
+        self.cancel();
+        let controller = AbortController::new().ok()?;
+        let signal = controller.signal();
+        *self.controller.borrow_mut() = Some(controller);
+        Some(signal)
+    }
+}
+
+fn is_aborted(signal: &Option<AbortSignal>) -> bool {
+    signal.as_ref().is_some_and(AbortSignal::aborted)
+}
+
+/// `send`, refreshing the token and retrying once when an authenticated request gets a 401 that
+/// the refresh predicate accepts
+async fn send_authenticated(request: Request) -> Result<Response> {
+    let Some(refresh) = TOKEN_REFRESH.with(|current| current.borrow().clone()) else {
+        return send(request).await;
+    };
+
+    // Sending consumes the body, so keep a copy for the retry
+    let request = web_sys::Request::from(request);
+    let retry = web_sys::Request::clone(&request).ok();
+
+    let response = send(Request::from(request)).await?;
+    if response.status() != 401 {
+        return Ok(response);
+    }
+
+    let should_refresh = REFRESH_PREDICATE
+        .with(|current| current.borrow().clone())
+        .is_none_or(|predicate| predicate(&response));
+    let Some(retry) = retry.filter(|_| should_refresh) else {
+        return Ok(response);
+    };
+    if !refresh().await {
+        return Ok(response);
+    }
+
+    let config = auth_config();
+    let header_value = config.header_value(&current_token().unwrap_or_default());
+    if retry
+        .headers()
+        .set(&config.header_name, &header_value)
+        .is_err()
+    {
+        return Ok(response);
+    }
+    send(Request::from(retry)).await
+}
+
 pub trait NetResponsive {
     async fn post_json<T, B>(self, body: B) -> Result<(Option<T>, Response)>
     where
@@ -15,6 +324,15 @@ pub trait NetResponsive {
     where
         T: Deserialize;
 
+    /// Like `get_response`, but aborts the request previously sent with `token`. Returns
+    /// `Canceled` if this request is itself aborted before its body is read
+    fn get_response_cancelable<T>(
+        self,
+        token: &CancelToken,
+    ) -> impl Future<Output = Result<(Option<T>, Response)>>
+    where
+        T: Deserialize;
+
     fn authenticate(self) -> Self;
 }
 
@@ -24,7 +342,7 @@ impl NetResponsive for RequestBuilder {
         T: Deserialize,
         B: Serialize,
     {
-        let result = self.authenticate().json_lite(&body)?.send().await?;
+        let result = send_authenticated(self.authenticate().json_lite(&body)?).await?;
 
         Ok((result.json_lite::<T>().await.ok(), result))
     }
@@ -33,19 +351,67 @@ impl NetResponsive for RequestBuilder {
     where
         T: Deserialize,
     {
-        let result = self.authenticate().send().await?;
+        let result = send_authenticated(self.authenticate().build()?).await?;
 
         Ok((result.json_lite::<T>().await.ok(), result))
     }
 
+    async fn get_response_cancelable<T>(self, token: &CancelToken) -> Result<(Option<T>, Response)>
+    where
+        T: Deserialize,
+    {
+        let signal = token.next_signal();
+        let request = self.authenticate().abort_signal(signal.as_ref()).build()?;
+
+        let result = match send_authenticated(request).await {
+            Ok(result) => result,
+            Err(_) if is_aborted(&signal) => return Err(Canceled.into()),
+            Err(error) => return Err(error),
+        };
+        let body = result.json_lite::<T>().await.ok();
+        if is_aborted(&signal) {
+            return Err(Canceled.into());
+        }
+
+        Ok((body, result))
+    }
+
     fn authenticate(self) -> Self {
+        let config = auth_config();
         self.header(
-            "Authorization",
-            &("Bearer ".to_string() + &get_cookie("auth-token").unwrap_or_default()),
+            &config.header_name,
+            &config.header_value(&current_token().unwrap_or_default()),
         )
     }
 }
 
+/// Serializes a serde-lite value to a JSON string, e.g. for `localStorage`
+pub fn to_json_lite<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(&value.serialize()?)?)
+}
+
+/// Deserializes a serde-lite value from a JSON string
+pub fn from_json_lite<T: Deserialize>(json: &str) -> Result<T> {
+    // Parse to Intermediate first, then deserialize with serde-lite
+    let intermediate: Intermediate = serde_json::from_str(json)?;
+    Ok(T::deserialize(&intermediate)?)
+}
+
+/// Like `from_json_lite`, but straight from bytes, e.g. a response body, without first copying
+/// them into a `String`. An empty or whitespace-only body is read as `{}`
+pub fn from_json_lite_slice<T: Deserialize>(json: &[u8]) -> Result<T> {
+    let intermediate: Intermediate = serde_json::from_slice(coalesce_blank(json))?;
+    Ok(T::deserialize(&intermediate)?)
+}
+
+fn coalesce_blank(json: &[u8]) -> &[u8] {
+    if json.iter().all(u8::is_ascii_whitespace) {
+        b"{}"
+    } else {
+        json
+    }
+}
+
 pub trait JsonLiteSerialize {
     fn json_lite<T: Serialize + ?Sized>(self, value: &T) -> Result<Request>;
 }
@@ -53,7 +419,7 @@ pub trait JsonLiteSerialize {
 impl JsonLiteSerialize for gloo_net::http::RequestBuilder {
     /// Sets the body and Content-Type header to json using serde-lite
     fn json_lite<T: Serialize + ?Sized>(self, value: &T) -> Result<Request> {
-        let json = serde_json::to_string(&value.serialize()?)?;
+        let json = to_json_lite(value)?;
         Ok(self.header("Content-Type", "application/json").body(json)?)
     }
 }
@@ -67,11 +433,149 @@ impl JsonLiteDeserialize for gloo_net::http::Response {
     /// If the returned response is a blank string
     /// The text response will be coalesced to "{}"
     async fn json_lite<T: Deserialize>(&self) -> Result<T> {
-        // blank return result equals crash
+        // Parse the raw bytes so large bodies aren't held twice as bytes and as a String
+        from_json_lite_slice(&self.binary().await?)
+    }
+}
+
+/// RFC 7807 `application/problem+json` error body
+#[derive(Debug, Clone, PartialEq, Default, serde_lite::Serialize, serde_lite::Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type", default)]
+    pub problem_type: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+/// Error for a non-2xx response
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseError {
+    /// The body was a parseable `application/problem+json` document
+    Problem(ProblemDetails),
+    /// Any other body, kept as raw text
+    Status { status: u16, body: String },
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseError::Problem(problem) => write!(
+                f,
+                "{}",
+                problem
+                    .detail
+                    .as_deref()
+                    .or(problem.title.as_deref())
+                    .unwrap_or("Request failed")
+            ),
+            ResponseError::Status { status, body } => write!(f, "{}: {}", status, body),
+        }
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+/// Returns `Ok` for 2xx responses, otherwise the body as `ProblemDetails` when the server sent
+/// `application/problem+json`, falling back to the raw text
+pub async fn error_for_status(response: &Response) -> Result<(), ResponseError> {
+    if response.ok() {
+        return Ok(());
+    }
+
+    let is_problem = response
+        .headers()
+        .get("Content-Type")
+        .is_some_and(|content_type| content_type.starts_with("application/problem+json"));
+    let body = response.text().await.unwrap_or_default();
 
-        let json_value = serde_json::from_str(&self.text().await?)?;
+    if is_problem && let Ok(problem) = from_json_lite::<ProblemDetails>(&body) {
+        return Err(ResponseError::Problem(problem));
+    }
+
+    Err(ResponseError::Status {
+        status: response.status(),
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        from_json_lite, from_json_lite_slice, reports_invalid_token, to_json_lite, AuthConfig,
+        Canceled, ProblemDetails,
+    };
+
+    #[test]
+    fn auth_scheme_defaults_to_bearer_and_can_be_dropped() {
+        assert_eq!(AuthConfig::default().header_value("abc"), "Bearer abc");
+
+        let bare = AuthConfig {
+            scheme: String::new(),
+            ..AuthConfig::default()
+        };
+        assert_eq!(bare.header_value("abc"), "abc");
+    }
+
+    #[test]
+    fn invalid_token_challenge_is_recognised() {
+        assert!(reports_invalid_token(
+            r#"Bearer realm="api", error="invalid_token", error_description="expired""#
+        ));
+        assert!(reports_invalid_token("Bearer error=invalid_token"));
+        assert!(!reports_invalid_token(
+            r#"Bearer realm="api", error="insufficient_scope""#
+        ));
+        assert!(!reports_invalid_token(r#"Bearer realm="api""#));
+    }
+
+    #[test]
+    fn canceled_is_distinguishable_through_anyhow() {
+        let error = anyhow::Error::from(Canceled);
+        assert!(error.is::<Canceled>());
+        assert!(!anyhow::anyhow!("Network error").is::<Canceled>());
+    }
+
+    #[test]
+    fn json_lite_round_trips() {
+        let problem = ProblemDetails {
+            problem_type: Some("https://example.com/probs/out-of-credit".to_string()),
+            title: Some("You do not have enough credit.".to_string()),
+            status: Some(403),
+            detail: None,
+            instance: None,
+        };
+
+        let json = to_json_lite(&problem).unwrap();
+        assert!(json.contains(r#""type":"https://example.com/probs/out-of-credit""#));
+        assert_eq!(from_json_lite::<ProblemDetails>(&json).unwrap(), problem);
+    }
+
+    #[test]
+    fn from_json_lite_slice_matches_str_and_coalesces_blank_bodies() {
+        let json = br#"{"title":"Export failed","status":500}"#;
+        assert_eq!(
+            from_json_lite_slice::<ProblemDetails>(json).unwrap(),
+            from_json_lite::<ProblemDetails>(std::str::from_utf8(json).unwrap()).unwrap()
+        );
+
+        assert_eq!(
+            from_json_lite_slice::<ProblemDetails>(b"").unwrap(),
+            ProblemDetails::default()
+        );
+        assert_eq!(
+            from_json_lite_slice::<ProblemDetails>(b" \n").unwrap(),
+            ProblemDetails::default()
+        );
+    }
 
-        // Convert JSON value to Intermediate, then deserialize with serde-lite
-        Ok(Deserialize::deserialize(&json_value)?)
+    #[test]
+    fn from_json_lite_rejects_invalid_json() {
+        assert!(from_json_lite::<ProblemDetails>("not json").is_err());
     }
 }
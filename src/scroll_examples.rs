@@ -1,9 +1,10 @@
+use crate::scroll_spy::SectionTracker;
 use crate::scroll_storyteller::{
-    create_element_storyteller_with_config, EasingFunction, ScrollStorytellerConfig,
+    create_element_storyteller_with_config, EasingFunction, ScrollStorytellerConfig, StyleBatch,
 };
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{Element, HtmlElement};
+use web_sys::Element;
 
 /// Example component demonstrating parallax scrolling within an element
 #[component]
@@ -18,25 +19,21 @@ pub fn ParallaxExample() -> impl IntoView {
             let config = ScrollStorytellerConfig {
                 throttle_ms: 8,
                 smooth_scroll: true,
-                offset_top: 0.0,
-                offset_bottom: 0.0,
                 run_straight_away: false,
                 resize_debounce_ms: 250,
+                ..Default::default()
             };
 
-            if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
-                config,
-            ) {
+            if let Ok(storyteller) =
+                create_element_storyteller_with_config(container.clone(), config)
+            {
                 // Create parallax effect that moves element slower than container scroll
                 storyteller.on_scroll(move |progress| {
                     let parallax_offset = progress.progress * -100.0; // Move up 100px over full scroll
-                    if let Ok(element_html) = element.clone().dyn_into::<HtmlElement>() {
-                        let _ = element_html.style().set_property(
-                            "transform",
-                            &format!("translateY({}px)", parallax_offset),
-                        );
-                    }
+                    StyleBatch::new()
+                        .set("transform", format!("translateY({}px)", parallax_offset))
+                        .set("opacity", 1.0 - progress.progress * 0.5)
+                        .flush(&element);
                 });
             }
         }
@@ -80,7 +77,7 @@ pub fn ScrollAnimationExample() -> impl IntoView {
     Effect::new(move |_| {
         if let Some(container) = container_ref.get_untracked() {
             if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
+                container.clone(),
                 ScrollStorytellerConfig::default(),
             ) {
                 // Fade in and scale up between 10% and 50% of container scroll
@@ -144,11 +141,11 @@ pub fn ScrollProgressIndicator() -> impl IntoView {
     Effect::new(move |_| {
         if let Some(container) = container_ref.get_untracked() {
             if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
+                container.clone(),
                 ScrollStorytellerConfig::default(),
             ) {
                 storyteller.on_scroll(move |progress| {
-                    progress_signal.set(progress.progress * 100.0);
+                    progress_signal.set(progress.percent());
                 });
             }
         }
@@ -200,25 +197,29 @@ pub fn ScrollProgressIndicator() -> impl IntoView {
 #[component]
 pub fn SectionScrollExample() -> impl IntoView {
     let container_ref = NodeRef::<leptos::html::Div>::new();
-    let current_section = RwSignal::new(1);
+    let current_section = RwSignal::new("1".to_string());
 
     Effect::new(move |_| {
         if let Some(container) = container_ref.get_untracked() {
             if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
+                container.clone(),
                 ScrollStorytellerConfig::default(),
             ) {
                 // Define section triggers with better boundaries
-                storyteller.on_progress_range(0.0, 0.3, move |_progress, _range| {
-                    current_section.set(1);
-                });
-
-                storyteller.on_progress_range(0.3, 0.7, move |_progress, _range| {
-                    current_section.set(2);
-                });
-
-                storyteller.on_progress_range(0.7, 1.0, move |_progress, _range| {
-                    current_section.set(3);
+                let sections = SectionTracker::new(
+                    &storyteller,
+                    vec![
+                        ("1".to_string(), 0.0, 0.3),
+                        ("2".to_string(), 0.3, 0.7),
+                        ("3".to_string(), 0.7, 1.0),
+                    ],
+                );
+
+                let active = sections.active();
+                Effect::new(move |_| {
+                    if let Some(section) = active.get() {
+                        current_section.set(section);
+                    }
                 });
             }
         }
@@ -276,7 +277,7 @@ pub fn ScrollToExample() -> impl IntoView {
     let scroll_to_section = move |progress: f64| {
         if let Some(container) = container_ref.get_untracked() {
             if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
+                container.clone(),
                 ScrollStorytellerConfig::default(),
             ) {
                 let _ = storyteller.scroll_to_progress(progress);
@@ -376,19 +377,24 @@ pub fn AdvancedScrollExample() -> impl IntoView {
     Effect::new(move |_| {
         if let Some(container) = container_ref.get_untracked() {
             if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
+                container.clone(),
                 ScrollStorytellerConfig::default(),
             ) {
                 storyteller.on_scroll(move |progress| {
                     // Change background hue through the scroll
-                    background_hue.set(200.0 + (progress.progress * 160.0)); // 200-360 degrees
+                    background_hue.set(progress.remap(0.0, 1.0, 200.0, 360.0));
 
                     // Scale text with easing
-                    let eased_progress = progress.eased(EasingFunction::EaseInOutCubic);
-                    text_scale.set(1.0 + (eased_progress * 1.5));
+                    text_scale.set(progress.eased_remap(
+                        0.0,
+                        1.0,
+                        1.0,
+                        2.5,
+                        EasingFunction::EaseInOutCubic,
+                    ));
 
                     // Rotate based on scroll
-                    rotation.set(progress.progress * 180.0);
+                    rotation.set(progress.remap(0.0, 1.0, 0.0, 180.0));
                 });
             }
         }
@@ -479,7 +485,7 @@ pub fn BasicUsageExample() -> impl IntoView {
 
             // Now try the storyteller
             if let Ok(storyteller) = create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
+                container.clone(),
                 ScrollStorytellerConfig::default(),
             ) {
                 web_sys::console::log_1(&"Storyteller created successfully!".into());
@@ -487,7 +493,7 @@ pub fn BasicUsageExample() -> impl IntoView {
 
                 storyteller.on_scroll(move |progress| {
                     web_sys::console::log_1(
-                        &format!("Storyteller scroll event: {:.3}", progress.progress).into(),
+                        &format!("Storyteller scroll event: {}", progress).into(),
                     );
                     scroll_progress.set(progress.progress);
                 });
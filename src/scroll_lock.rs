@@ -0,0 +1,170 @@
+use std::cell::{Cell, RefCell};
+
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use crate::extensions::document_extensions::DocumentExtensions;
+
+/// Reference count of active locks plus the inline styles they replaced, so stacked modals only
+/// unlock the page once the last one closes
+#[derive(Default)]
+struct ScrollLock {
+    count: usize,
+    overflow: String,
+    padding_right: String,
+}
+
+impl ScrollLock {
+    /// Returns true when this is the first lock and the styles need applying
+    fn acquire(&mut self) -> bool {
+        self.count += 1;
+        self.count == 1
+    }
+
+    /// Returns true when this released the last lock and the styles need restoring. Unbalanced
+    /// unlocks are ignored
+    fn release(&mut self) -> bool {
+        if self.count == 0 {
+            return false;
+        }
+
+        self.count -= 1;
+        self.count == 0
+    }
+}
+
+thread_local! {
+    static BODY_SCROLL_LOCK: RefCell<ScrollLock> = RefCell::default();
+    static SCROLLBAR_WIDTH: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+/// Width in px of the platform's classic scrollbar, e.g. to offset custom scrollbar overlays.
+/// 0.0 where scrollbars overlay the content (macOS, mobile) or without a document. Measured
+/// once with an offscreen scrolling div and cached
+pub fn scrollbar_width() -> f64 {
+    if let Some(width) = SCROLLBAR_WIDTH.get() {
+        return width;
+    }
+
+    let Some(width) = measure_scrollbar_width() else {
+        return 0.0;
+    };
+    SCROLLBAR_WIDTH.set(Some(width));
+    width
+}
+
+fn measure_scrollbar_width() -> Option<f64> {
+    let document = web_sys::window()?.document()?;
+    let body = document.body()?;
+    let probe = document.create_element_unchecked::<HtmlElement>("div")?;
+    let _ = probe.set_attribute(
+        "style",
+        "position: absolute; top: -9999px; width: 100px; height: 100px; overflow: scroll; \
+         visibility: hidden;",
+    );
+
+    body.append_child(&probe).ok()?;
+    let width = (probe.offset_width() - probe.client_width()).max(0) as f64;
+    probe.remove();
+
+    Some(width)
+}
+
+fn page_elements() -> Option<(HtmlElement, HtmlElement)> {
+    let document = web_sys::window()?.document()?;
+    let root = document
+        .document_element()?
+        .dyn_into::<HtmlElement>()
+        .ok()?;
+    Some((root, document.body()?))
+}
+
+/// Stops the page behind a modal from scrolling by hiding overflow on the document element.
+/// The scrollbar's width is added to the body's right padding so content doesn't shift when it
+/// disappears. Every call must be paired with `unlock_body_scroll`
+pub fn lock_body_scroll() {
+    let Some((root, body)) = page_elements() else {
+        return;
+    };
+
+    BODY_SCROLL_LOCK.with_borrow_mut(|lock| {
+        if !lock.acquire() {
+            return;
+        }
+
+        let root_style = root.style();
+        let body_style = body.style();
+        lock.overflow = root_style
+            .get_property_value("overflow")
+            .unwrap_or_default();
+        lock.padding_right = body_style
+            .get_property_value("padding-right")
+            .unwrap_or_default();
+
+        // Measure before hiding overflow, the scrollbar is gone afterwards
+        let scrollbar_width = web_sys::window()
+            .and_then(|window| window.inner_width().ok())
+            .and_then(|width| width.as_f64())
+            .map(|width| (width - root.client_width() as f64).max(0.0))
+            .unwrap_or(0.0);
+
+        let _ = root_style.set_property("overflow", "hidden");
+        if scrollbar_width > 0.0 {
+            let _ = body_style.set_property("padding-right", &format!("{}px", scrollbar_width));
+        }
+    });
+}
+
+/// Releases a lock taken by `lock_body_scroll`, restoring the page's original styles once no
+/// locks remain
+pub fn unlock_body_scroll() {
+    let Some((root, body)) = page_elements() else {
+        return;
+    };
+
+    BODY_SCROLL_LOCK.with_borrow_mut(|lock| {
+        if !lock.release() {
+            return;
+        }
+
+        restore_property(&root, "overflow", &lock.overflow);
+        restore_property(&body, "padding-right", &lock.padding_right);
+    });
+}
+
+/// Whether any `lock_body_scroll` is still active
+pub fn is_body_scroll_locked() -> bool {
+    BODY_SCROLL_LOCK.with_borrow(|lock| lock.count > 0)
+}
+
+fn restore_property(element: &HtmlElement, name: &str, value: &str) {
+    let style = element.style();
+    if value.is_empty() {
+        let _ = style.remove_property(name);
+    } else {
+        let _ = style.set_property(name, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollLock;
+
+    #[test]
+    fn nested_locks_only_unlock_after_the_last_release() {
+        let mut lock = ScrollLock::default();
+
+        assert!(lock.acquire());
+        assert!(!lock.acquire());
+        assert!(!lock.release());
+        assert!(lock.release());
+    }
+
+    #[test]
+    fn unbalanced_release_is_ignored() {
+        let mut lock = ScrollLock::default();
+
+        assert!(!lock.release());
+        assert!(lock.acquire());
+    }
+}
@@ -0,0 +1,161 @@
+use leptos::prelude::*;
+use web_sys::Element;
+
+use crate::intersection_observer::{IntersectionConfig, Intersectioner, ObserverCallback};
+use crate::scroll_storyteller::{ranges_containing, ScrollStoryteller};
+
+/// Thresholds used when the config doesn't provide its own, so ratios update as sections move
+const DEFAULT_SPY_THRESHOLDS: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// Intersection ratio and viewport top of one section as last reported by the observer
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+struct SectionVisibility {
+    ratio: f64,
+    top: f64,
+}
+
+/// Tracks which of a set of sections is most prominent in the viewport, e.g. for highlighting
+/// the current entry in a table of contents
+pub struct ScrollSpy {
+    _observer: Intersectioner,
+    active_id: Memo<Option<String>>,
+}
+
+impl ScrollSpy {
+    pub fn new(sections: Vec<(String, Element)>, config: IntersectionConfig) -> Self {
+        let config = if config.thresholds.is_empty() {
+            config.thresholds(DEFAULT_SPY_THRESHOLDS.to_vec())
+        } else {
+            config
+        };
+        let observer = Intersectioner::new(config);
+        let visibility = RwSignal::new(vec![SectionVisibility::default(); sections.len()]);
+
+        let mut ids = Vec::with_capacity(sections.len());
+        for (index, (id, element)) in sections.into_iter().enumerate() {
+            ids.push(id);
+
+            observer.register(
+                &element,
+                ObserverCallback::new(false, || {}).with_entry(move |entry| {
+                    let section = SectionVisibility {
+                        ratio: if entry.is_intersecting() {
+                            entry.intersection_ratio()
+                        } else {
+                            0.0
+                        },
+                        top: entry.bounding_client_rect().top(),
+                    };
+                    visibility.update(|visibility| visibility[index] = section);
+                }),
+            );
+        }
+
+        let active_id = Memo::new(move |_| {
+            visibility.with(|visibility| most_prominent(visibility).map(|index| ids[index].clone()))
+        });
+
+        Self {
+            _observer: observer,
+            active_id,
+        }
+    }
+
+    /// Id of the section with the highest intersection ratio, ties going to the topmost section
+    #[inline(always)]
+    pub fn active_id(&self) -> Memo<Option<String>> {
+        self.active_id
+    }
+}
+
+/// Tracks which of a set of named progress ranges a storyteller is in, e.g. to label the current
+/// chapter of a scroll story. Sections are `(name, from, to)`
+pub struct SectionTracker {
+    active: Memo<Option<String>>,
+}
+
+impl SectionTracker {
+    pub fn new(storyteller: &ScrollStoryteller, sections: Vec<(String, f64, f64)>) -> Self {
+        let progress = storyteller.progress();
+        let active = Memo::new(move |_| active_section(&sections, progress.get().progress));
+
+        Self { active }
+    }
+
+    /// Name of the section containing the current progress, the first listed when ranges
+    /// overlap and `None` in gaps between them
+    #[inline(always)]
+    pub fn active(&self) -> Memo<Option<String>> {
+        self.active
+    }
+}
+
+fn active_section(sections: &[(String, f64, f64)], progress: f64) -> Option<String> {
+    ranges_containing(sections, progress, |(_, from, to)| (*from, *to))
+        .next()
+        .map(|(name, _, _)| name.clone())
+}
+
+fn most_prominent(sections: &[SectionVisibility]) -> Option<usize> {
+    sections
+        .iter()
+        .enumerate()
+        .filter(|(_, section)| section.ratio > 0.0)
+        .min_by(|(_, a), (_, b)| {
+            b.ratio
+                .total_cmp(&a.ratio)
+                .then_with(|| a.top.total_cmp(&b.top))
+        })
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{active_section, most_prominent, SectionVisibility};
+
+    fn section(ratio: f64, top: f64) -> SectionVisibility {
+        SectionVisibility { ratio, top }
+    }
+
+    #[test]
+    fn highest_ratio_wins() {
+        let sections = [
+            section(0.25, -100.0),
+            section(0.75, 200.0),
+            section(0.0, 900.0),
+        ];
+        assert_eq!(most_prominent(&sections), Some(1));
+    }
+
+    #[test]
+    fn ties_resolve_to_topmost() {
+        let sections = [section(1.0, 400.0), section(1.0, 50.0)];
+        assert_eq!(most_prominent(&sections), Some(1));
+    }
+
+    #[test]
+    fn nothing_visible_is_none() {
+        let sections = [section(0.0, 0.0), section(0.0, 500.0)];
+        assert_eq!(most_prominent(&sections), None);
+    }
+
+    fn chapters() -> Vec<(String, f64, f64)> {
+        vec![
+            ("intro".to_string(), 0.0, 0.3),
+            ("middle".to_string(), 0.2, 0.6),
+            ("end".to_string(), 0.8, 1.0),
+        ]
+    }
+
+    #[test]
+    fn overlapping_sections_prefer_the_first() {
+        assert_eq!(active_section(&chapters(), 0.25), Some("intro".to_string()));
+        assert_eq!(active_section(&chapters(), 0.5), Some("middle".to_string()));
+    }
+
+    #[test]
+    fn gaps_between_sections_are_none() {
+        assert_eq!(active_section(&chapters(), 0.7), None);
+        assert_eq!(active_section(&chapters(), 1.0), Some("end".to_string()));
+    }
+}
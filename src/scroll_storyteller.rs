@@ -1,19 +1,44 @@
+use crate::cleanup::on_cleanup_local;
+use crate::media::matches_media;
+use crate::storage::session_storage;
+use crate::timing::{debounce, throttle};
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_core::Stream;
 use leptos::ev::resize;
 use leptos::prelude::*;
 use std::cell::Cell;
+use std::fmt;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::HtmlElement;
-use web_sys::{ScrollBehavior, ScrollToOptions};
+use web_sys::{
+    AddEventListenerOptions, Element, Event, EventTarget, HtmlElement, KeyboardEvent,
+    MutationObserver, MutationObserverInit, PointerEvent, ResizeObserver, WheelEvent,
+};
+use web_sys::{Performance, ScrollBehavior, ScrollToOptions};
 
-/// Normalized scroll progress (0.0-1.0)
+/// Suggested epsilon for `ScrollStoryteller::on_progress_change`
+pub const DEFAULT_PROGRESS_EPSILON: f64 = 0.001;
+
+/// Below this a scroll event is treated as not having moved and isn't dispatched
+const UNCHANGED_EPSILON: f64 = 1e-6;
+
+/// Distance in px within which `smoothing` snaps the displayed position to the real one and stops
+const SMOOTHING_EPSILON_PX: f64 = 0.5;
+
+/// Normalized scroll progress (0.0-1.0). The derived `PartialEq` compares every field exactly,
+/// use `approx_eq` for change detection that shouldn't trip over float rounding
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct ScrollProgress {
     pub progress: f64,
     pub scroll_y: f64,
     pub scroll_height: f64,
     pub viewport_height: f64,
+    /// Signed distance in px that `scroll_y` overshoots the scrollable range by (e.g. iOS
+    /// rubber banding), negative above the top and positive past the bottom, otherwise 0.0
+    pub overscroll: f64,
 }
 
 impl ScrollProgress {
@@ -24,6 +49,7 @@ impl ScrollProgress {
             scroll_y,
             scroll_height,
             viewport_height,
+            overscroll: 0f64,
         };
 
         new_progress.calculate_progress();
@@ -33,7 +59,14 @@ impl ScrollProgress {
 
     #[inline(always)]
     pub fn calculate_progress(&mut self) {
-        let max_scroll = (self.scroll_height - self.viewport_height).max(0.0);
+        let max_scroll = self.max_scroll();
+        self.overscroll = if self.scroll_y < 0.0 {
+            self.scroll_y
+        } else if self.scroll_y > max_scroll {
+            self.scroll_y - max_scroll
+        } else {
+            0.0
+        };
         self.progress = if max_scroll > 0.0 {
             (self.scroll_y / max_scroll).clamp(self.viewport_height / self.scroll_height, 1.0)
         } else {
@@ -41,34 +74,44 @@ impl ScrollProgress {
         };
     }
 
+    /// Whether every field is within `epsilon` of `other`'s
     #[inline(always)]
-    pub fn eased(&self, easing: EasingFunction) -> f64 {
-        match easing {
-            EasingFunction::Linear => self.progress,
-            EasingFunction::EaseIn => self.progress * self.progress,
-            EasingFunction::EaseOut => 1.0 - (1.0 - self.progress) * (1.0 - self.progress),
-            EasingFunction::EaseInOut => {
-                if self.progress < 0.5 {
-                    2.0 * self.progress * self.progress
-                } else {
-                    let t = -2.0 * self.progress + 2.0;
-                    1.0 - t * t / 2.0
-                }
-            }
-            EasingFunction::EaseInCubic => self.progress * self.progress * self.progress,
-            EasingFunction::EaseOutCubic => {
-                let t = 1.0 - self.progress;
-                1.0 - t * t * t
-            }
-            EasingFunction::EaseInOutCubic => {
-                if self.progress < 0.5 {
-                    4.0 * self.progress * self.progress * self.progress
-                } else {
-                    let t = -2.0 * self.progress + 2.0;
-                    1.0 - t * t * t / 2.0
-                }
-            }
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        (self.progress - other.progress).abs() <= epsilon
+            && (self.scroll_y - other.scroll_y).abs() <= epsilon
+            && (self.scroll_height - other.scroll_height).abs() <= epsilon
+            && (self.viewport_height - other.viewport_height).abs() <= epsilon
+            && (self.overscroll - other.overscroll).abs() <= epsilon
+    }
+
+    /// Scrollable distance in px, 0.0 when the content fits the viewport
+    #[inline(always)]
+    pub fn max_scroll(&self) -> f64 {
+        (self.scroll_height - self.viewport_height).max(0.0)
+    }
+
+    /// Pixels scrolled since `previous`, positive going down. 0.0 when the content or viewport was
+    /// resized in between, as progress moving then isn't the user scrolling
+    #[inline(always)]
+    pub fn scroll_delta(&self, previous: &Self) -> f64 {
+        let resized = previous.scroll_height != self.scroll_height
+            || previous.viewport_height != self.viewport_height;
+        if resized {
+            return 0.0;
         }
+
+        self.scroll_y - previous.scroll_y
+    }
+
+    /// Whether the content overflows the viewport
+    #[inline(always)]
+    pub fn is_scrollable(&self) -> bool {
+        self.scroll_height > self.viewport_height
+    }
+
+    #[inline(always)]
+    pub fn eased(&self, easing: EasingFunction) -> f64 {
+        easing.apply(self.progress)
     }
 
     #[inline(always)]
@@ -86,9 +129,83 @@ impl ScrollProgress {
     pub fn is_in_range(&self, from: f64, to: f64) -> bool {
         self.progress >= from && self.progress <= to
     }
+
+    /// Maps progress within `[in_from, in_to]` linearly onto `[out_from, out_to]`,
+    /// e.g. `remap(0.2, 0.8, 100.0, -50.0)` for a translate
+    #[inline(always)]
+    pub fn remap(&self, in_from: f64, in_to: f64, out_from: f64, out_to: f64) -> f64 {
+        out_from + (out_to - out_from) * self.in_range(in_from, in_to)
+    }
+
+    /// `remap` with `easing` applied to the progress within `[in_from, in_to]`
+    #[inline(always)]
+    pub fn eased_remap(
+        &self,
+        in_from: f64,
+        in_to: f64,
+        out_from: f64,
+        out_to: f64,
+        easing: EasingFunction,
+    ) -> f64 {
+        let range_progress = Self {
+            progress: self.in_range(in_from, in_to),
+            ..*self
+        };
+        out_from + (out_to - out_from) * range_progress.eased(easing)
+    }
+
+    /// Whole number counting from 0 to `target` as progress moves through `[from, to]`, e.g. a
+    /// stat counting up as it scrolls into view. Pass `EasingFunction::Linear` for a steady count.
+    /// Truncates toward 0 so the count only lands on `target` at the end of the range, and lands
+    /// on it exactly there
+    #[inline(always)]
+    pub fn counter_value(&self, from: f64, to: f64, target: i64, easing: EasingFunction) -> i64 {
+        let t = easing.apply(self.in_range(from, to));
+        if t >= 1.0 {
+            return target;
+        }
+        (target as f64 * t).trunc() as i64
+    }
+
+    /// Progress as a percentage, 0.0-100.0
+    #[inline(always)]
+    pub fn percent(&self) -> f64 {
+        self.progress * 100.0
+    }
+
+    /// `percent` rounded to `decimals` places, e.g. for `format!("{}%", ...)` labels
+    #[inline(always)]
+    pub fn percent_rounded(&self, decimals: u32) -> f64 {
+        let factor = 10f64.powi(decimals as i32);
+        (self.percent() * factor).round() / factor
+    }
+}
+
+/// Compact summary for logs, e.g. `p=0.42 (y=320/760, vp=360)` with `y` against the scrollable
+/// distance
+impl fmt::Display for ScrollProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "p={:.2} (y={:.0}/{:.0}, vp={:.0})",
+            self.progress,
+            self.scroll_y,
+            self.max_scroll(),
+            self.viewport_height
+        )
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+    serde_lite::Serialize,
+    serde_lite::Deserialize,
+)]
 pub enum EasingFunction {
     Linear,
     EaseIn,
@@ -97,201 +214,875 @@ pub enum EasingFunction {
     EaseInCubic,
     EaseOutCubic,
     EaseInOutCubic,
+    EaseInQuart,
+    EaseOutQuart,
+    EaseInOutQuart,
+    EaseInQuint,
+    EaseOutQuint,
+    EaseInOutQuint,
+    EaseInExpo,
+    EaseOutExpo,
+    EaseInOutExpo,
+    EaseInCirc,
+    EaseOutCirc,
+    EaseInOutCirc,
 }
 
-#[derive(Clone)]
+impl EasingFunction {
+    /// Eases `x` (expected to be in 0.0-1.0), for animating values other than scroll progress
+    #[inline(always)]
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            EasingFunction::Linear => x,
+            EasingFunction::EaseIn => x * x,
+            EasingFunction::EaseOut => 1.0 - (1.0 - x) * (1.0 - x),
+            EasingFunction::EaseInOut => {
+                if x < 0.5 {
+                    2.0 * x * x
+                } else {
+                    let t = -2.0 * x + 2.0;
+                    1.0 - t * t / 2.0
+                }
+            }
+            EasingFunction::EaseInCubic => x * x * x,
+            EasingFunction::EaseOutCubic => {
+                let t = 1.0 - x;
+                1.0 - t * t * t
+            }
+            EasingFunction::EaseInOutCubic => {
+                if x < 0.5 {
+                    4.0 * x * x * x
+                } else {
+                    let t = -2.0 * x + 2.0;
+                    1.0 - t * t * t / 2.0
+                }
+            }
+            EasingFunction::EaseInQuart => x.powi(4),
+            EasingFunction::EaseOutQuart => 1.0 - (1.0 - x).powi(4),
+            EasingFunction::EaseInOutQuart => {
+                if x < 0.5 {
+                    8.0 * x.powi(4)
+                } else {
+                    1.0 - (-2.0 * x + 2.0).powi(4) / 2.0
+                }
+            }
+            EasingFunction::EaseInQuint => x.powi(5),
+            EasingFunction::EaseOutQuint => 1.0 - (1.0 - x).powi(5),
+            EasingFunction::EaseInOutQuint => {
+                if x < 0.5 {
+                    16.0 * x.powi(5)
+                } else {
+                    1.0 - (-2.0 * x + 2.0).powi(5) / 2.0
+                }
+            }
+            // Expo never quite reaches its endpoints, so they're pinned explicitly
+            EasingFunction::EaseInExpo => {
+                if x <= 0.0 {
+                    0.0
+                } else {
+                    2f64.powf(10.0 * x - 10.0)
+                }
+            }
+            EasingFunction::EaseOutExpo => {
+                if x >= 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2f64.powf(-10.0 * x)
+                }
+            }
+            EasingFunction::EaseInOutExpo => {
+                if x <= 0.0 {
+                    0.0
+                } else if x >= 1.0 {
+                    1.0
+                } else if x < 0.5 {
+                    2f64.powf(20.0 * x - 10.0) / 2.0
+                } else {
+                    (2.0 - 2f64.powf(-20.0 * x + 10.0)) / 2.0
+                }
+            }
+            EasingFunction::EaseInCirc => 1.0 - (1.0 - x.powi(2)).sqrt(),
+            EasingFunction::EaseOutCirc => (1.0 - (x - 1.0).powi(2)).sqrt(),
+            EasingFunction::EaseInOutCirc => {
+                if x < 0.5 {
+                    (1.0 - (1.0 - (2.0 * x).powi(2)).sqrt()) / 2.0
+                } else {
+                    ((1.0 - (-2.0 * x + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+                }
+            }
+        }
+    }
+
+    /// `(x, apply(x))` at `steps + 1` evenly spaced points from 0.0 to 1.0, e.g. to draw the
+    /// curve in an easing editor
+    pub fn sample_curve(self, steps: usize) -> Vec<(f64, f64)> {
+        let steps = steps.max(1);
+        (0..=steps)
+            .map(|step| {
+                let x = step as f64 / steps as f64;
+                (x, self.apply(x))
+            })
+            .collect()
+    }
+}
+
+/// `easing` sampled at `steps + 1` evenly spaced points from 0.0 to 1.0, as
+/// `EasingFunction::sample_curve`, for tooling that holds the easing by value
+#[inline(always)]
+pub fn sample_easing(easing: EasingFunction, steps: usize) -> Vec<(f64, f64)> {
+    easing.sample_curve(steps)
+}
+
+/// How scroll events are gated before callbacks are dispatched
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Debug,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    serde_lite::Serialize,
+    serde_lite::Deserialize,
+)]
+pub enum ScrollMode {
+    /// Dispatch at most once every `throttle_ms`
+    #[default]
+    Throttle,
+    /// Dispatch once after `throttle_ms` of no further scroll events
+    Debounce,
+}
+
+/// A config offset, either absolute or relative to the scroll container's visible height so it
+/// tracks responsive layouts, e.g. `Percent(10.0)` for a `10vh` sticky header on the page
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+    serde_lite::Serialize,
+    serde_lite::Deserialize,
+)]
+pub enum OffsetUnit {
+    Pixels(f64),
+    /// Percentage (0.0-100.0) of the container's `client_height`
+    Percent(f64),
+}
+
+impl OffsetUnit {
+    /// The offset in pixels for a container `client_height` px tall
+    #[inline(always)]
+    pub fn resolve(self, client_height: f64) -> f64 {
+        match self {
+            OffsetUnit::Pixels(pixels) => pixels,
+            OffsetUnit::Percent(percent) => client_height * percent / 100.0,
+        }
+    }
+}
+
+impl Default for OffsetUnit {
+    fn default() -> Self {
+        OffsetUnit::Pixels(0.0)
+    }
+}
+
+impl From<f64> for OffsetUnit {
+    fn from(pixels: f64) -> Self {
+        OffsetUnit::Pixels(pixels)
+    }
+}
+
+#[derive(
+    Clone,
+    PartialEq,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+    serde_lite::Serialize,
+    serde_lite::Deserialize,
+)]
 pub struct ScrollStorytellerConfig {
     pub throttle_ms: u32,
+    pub scroll_mode: ScrollMode,
+    /// In `ScrollMode::Throttle`, also dispatch once at the end of a throttle window that
+    /// dropped events, so callbacks always see the final position
+    pub trailing: bool,
     pub smooth_scroll: bool,
-    pub offset_top: f64,
-    pub offset_bottom: f64,
+    pub offset_top: OffsetUnit,
+    pub offset_bottom: OffsetUnit,
     pub run_straight_away: bool,
     pub resize_debounce_ms: u32,
+    /// Lets `animate_css_property` use a native CSS scroll-driven animation where the browser
+    /// supports one
+    pub css_scroll_timeline: bool,
+    /// Scrollable distance in px below which the container counts as not scrollable at all:
+    /// progress stays 0.0 and callbacks don't run (bar `run_straight_away`), rather than content
+    /// that just fits jumping straight to 1.0. 0.0 disables it
+    pub min_scrollable_px: f64,
+    /// Re-measure on window resize. Turn off for containers whose size never changes to skip
+    /// the listener, calling `refresh` by hand if content does change size
+    pub track_resize: bool,
+    /// Fraction (0.0-1.0) of the remaining distance the progress handed to callbacks covers
+    /// each animation frame, easing toward the real scroll position rather than jumping with
+    /// every event. `None`, or a value outside (0.0, 1.0), dispatches the raw position. The
+    /// `progress()` signal always holds the raw position
+    pub smoothing: Option<f64>,
 }
 
 impl Default for ScrollStorytellerConfig {
     fn default() -> Self {
         Self {
             throttle_ms: 8,
+            scroll_mode: ScrollMode::Throttle,
+            trailing: false,
             smooth_scroll: true,
-            offset_top: 0.0,
-            offset_bottom: 0.0,
+            offset_top: OffsetUnit::Pixels(0.0),
+            offset_bottom: OffsetUnit::Pixels(0.0),
             run_straight_away: false,
             resize_debounce_ms: 250,
+            css_scroll_timeline: true,
+            min_scrollable_px: 0.0,
+            track_resize: true,
+            smoothing: None,
+        }
+    }
+}
+
+/// Errors that can occur while setting up a storyteller
+#[derive(Debug, Clone)]
+pub enum ScrollError {
+    NoWindow,
+    NoDocument,
+    /// None of `scrollingElement`, `documentElement` or `body` resolved
+    NoScrollingElement,
+    Js(JsValue),
+}
+
+impl fmt::Display for ScrollError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrollError::NoWindow => write!(f, "No window"),
+            ScrollError::NoDocument => write!(f, "No document"),
+            ScrollError::NoScrollingElement => write!(f, "No scrolling element"),
+            ScrollError::Js(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+impl From<JsValue> for ScrollError {
+    fn from(value: JsValue) -> Self {
+        ScrollError::Js(value)
+    }
+}
+
+impl From<ScrollError> for JsValue {
+    fn from(error: ScrollError) -> Self {
+        match error {
+            ScrollError::Js(value) => value,
+            other => JsValue::from_str(&other.to_string()),
+        }
+    }
+}
+
+/// A transition reported by `RangeTracker`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RangeEvent {
+    Enter,
+    Exit,
+    /// Progress jumped from one side of the range to the other between two updates, so it both
+    /// entered and exited
+    Crossed,
+}
+
+impl RangeEvent {
+    #[inline(always)]
+    pub fn is_enter(self) -> bool {
+        matches!(self, RangeEvent::Enter | RangeEvent::Crossed)
+    }
+
+    #[inline(always)]
+    pub fn is_exit(self) -> bool {
+        matches!(self, RangeEvent::Exit | RangeEvent::Crossed)
+    }
+}
+
+/// Which way the content moved between two updates
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+}
+
+impl ScrollDirection {
+    /// Direction from `previous` to `current`, `None` if the position didn't change or the
+    /// content or viewport was resized in between, as progress moving then isn't the user scrolling
+    pub fn between(previous: &ScrollProgress, current: &ScrollProgress) -> Option<Self> {
+        let delta = current.scroll_delta(previous);
+        if delta > 0.0 {
+            Some(ScrollDirection::Down)
+        } else if delta < 0.0 {
+            Some(ScrollDirection::Up)
+        } else {
+            None
+        }
+    }
+}
+
+/// The enter/exit state machine behind `on_enter_range`/`on_exit_range`, free of the DOM
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RangeTracker {
+    from: f64,
+    to: f64,
+    last_progress: Option<f64>,
+}
+
+impl RangeTracker {
+    pub fn new(from: f64, to: f64) -> Self {
+        Self {
+            from,
+            to,
+            last_progress: None,
+        }
+    }
+
+    /// Records `progress` and reports how it moved relative to the range. The first update
+    /// reports `Enter` if it starts in range and never reports an exit
+    pub fn update(&mut self, progress: f64) -> Option<RangeEvent> {
+        let is_in_range = self.contains(progress);
+        let Some(last_progress) = self.last_progress.replace(progress) else {
+            return is_in_range.then_some(RangeEvent::Enter);
+        };
+
+        match (self.contains(last_progress), is_in_range) {
+            (false, true) => Some(RangeEvent::Enter),
+            (true, false) => Some(RangeEvent::Exit),
+            // Outside both times but on opposite sides
+            (false, false) if (last_progress < self.from) != (progress < self.from) => {
+                Some(RangeEvent::Crossed)
+            }
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    fn contains(&self, progress: f64) -> bool {
+        progress >= self.from && progress <= self.to
+    }
+}
+
+/// Tracks which side of a single point progress is on, behind `on_cross`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CrossingTracker {
+    point: f64,
+    direction: ScrollDirection,
+    past_point: Option<bool>,
+}
+
+impl CrossingTracker {
+    pub fn new(point: f64, direction: ScrollDirection) -> Self {
+        Self {
+            point,
+            direction,
+            past_point: None,
+        }
+    }
+
+    /// Records `progress` and reports whether it just crossed the point in the tracked direction.
+    /// The first update only records the side, so starting past the point never fires
+    pub fn update(&mut self, progress: f64) -> bool {
+        let past_point = progress >= self.point;
+        let Some(was_past_point) = self.past_point.replace(past_point) else {
+            return false;
+        };
+
+        match self.direction {
+            ScrollDirection::Down => !was_past_point && past_point,
+            ScrollDirection::Up => was_past_point && !past_point,
+        }
+    }
+}
+
+type TaggedCallback = (Option<Rc<str>>, Box<dyn Fn(ScrollProgress)>);
+
+/// Scroll callbacks with the tag they were registered under, if any
+struct TaggedCallbacks {
+    callbacks: std::cell::RefCell<Vec<TaggedCallback>>,
+    /// Tags removed while the callbacks were running, removed once they've all run
+    pending_removals: std::cell::RefCell<Vec<Rc<str>>>,
+}
+
+impl TaggedCallbacks {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            callbacks: std::cell::RefCell::new(Vec::with_capacity(capacity)),
+            pending_removals: std::cell::RefCell::new(Vec::new()),
+        }
+    }
+
+    fn push(&self, tag: Option<Rc<str>>, callback: Box<dyn Fn(ScrollProgress)>) {
+        self.callbacks.borrow_mut().push((tag, callback));
+    }
+
+    /// Runs every callback with `progress`, then applies removals they made along the way
+    #[inline(always)]
+    fn dispatch(&self, progress: ScrollProgress) {
+        for (_, callback) in self.callbacks.borrow().iter() {
+            callback(progress);
+        }
+
+        if !self.pending_removals.borrow().is_empty() {
+            self.apply_pending_removals();
         }
     }
+
+    /// Removes the callbacks under `tag`, or queues the removal while they're running, e.g. for a
+    /// one-shot callback removing itself
+    fn remove_tagged(&self, tag: &str) {
+        self.pending_removals.borrow_mut().push(Rc::from(tag));
+        self.apply_pending_removals();
+    }
+
+    /// A no-op while a dispatch is still running, the outermost one applies them once it's done
+    fn apply_pending_removals(&self) {
+        let Ok(mut callbacks) = self.callbacks.try_borrow_mut() else {
+            return;
+        };
+
+        let tags = self.pending_removals.take();
+        callbacks.retain(|(callback_tag, _)| {
+            !callback_tag
+                .as_ref()
+                .is_some_and(|callback_tag| tags.contains(callback_tag))
+        });
+    }
+}
+
+/// What a storyteller's progress measures
+#[derive(Clone)]
+enum Tracking {
+    /// How far its element is scrolled
+    Container,
+    /// This element's passage through the window's viewport, while the storyteller's own
+    /// element is the page's scrolling element
+    Viewport(Element),
+}
+
+/// A range registered with `on_named_progress_range`, kept for `active_ranges`
+struct NamedRange {
+    /// Tag it was registered under, so `remove_tagged` forgets it with its callback
+    tag: Option<Rc<str>>,
+    name: String,
+    from: f64,
+    to: f64,
 }
 
 pub struct ScrollStoryteller {
-    element: HtmlElement,
+    id: usize,
+    element: Element,
+    tracking: Tracking,
     config: ScrollStorytellerConfig,
     last_progress: RwSignal<ScrollProgress>,
-    callbacks: Rc<std::cell::RefCell<Vec<Box<dyn Fn(ScrollProgress)>>>>,
+    is_scrollable: RwSignal<bool>,
+    callbacks: Rc<TaggedCallbacks>,
+    /// Tag given to callbacks registered inside `tagged`
+    registering_tag: std::cell::RefCell<Option<Rc<str>>>,
     last_scroll_time: Rc<Cell<f64>>,
     last_resize_time: Rc<Cell<f64>>,
+    animation_frame: Rc<Cell<Option<AnimationFrameRequestHandle>>>,
+    smoother: Option<ProgressSmoother<ScrollProgress>>,
+    /// The scroll listener's `throttle_ms`, read on every event
+    throttle_ms: Rc<Cell<f64>>,
+    /// Feeding each `progress_stream`, closed on cleanup to end them
+    stream_senders: Rc<std::cell::RefCell<Vec<UnboundedSender<ScrollProgress>>>>,
+    named_ranges: std::cell::RefCell<Vec<NamedRange>>,
 }
 
 impl ScrollStoryteller {
+    /// Tracks any scrollable element, an `HtmlElement` or e.g. an overflowing SVG, through the
+    /// scroll metrics every `Element` exposes
     pub fn new(
-        element: HtmlElement,
+        element: impl Into<Element>,
+        config: Option<ScrollStorytellerConfig>,
+    ) -> Result<Self, JsValue> {
+        let element = element.into();
+        let scroll_target = element.clone().unchecked_into::<EventTarget>();
+        Self::with_scroll_target(element, scroll_target, Tracking::Container, config)
+    }
+
+    /// Measures `element` but listens for scroll events on `scroll_target`, which differ for
+    /// the page where the scrolling element doesn't receive its own scroll events
+    fn with_scroll_target(
+        element: Element,
+        scroll_target: EventTarget,
+        tracking: Tracking,
         config: Option<ScrollStorytellerConfig>,
     ) -> Result<Self, JsValue> {
         let config = config.unwrap_or_default();
         let last_progress = RwSignal::new(ScrollProgress::new(0.0, 0.0, 0.0));
-        let callbacks: Rc<std::cell::RefCell<Vec<Box<dyn Fn(ScrollProgress)>>>> =
-            Rc::new(std::cell::RefCell::new(Vec::with_capacity(8)));
+        let is_scrollable = RwSignal::new(false);
+        let callbacks = Rc::new(TaggedCallbacks::with_capacity(8));
         let last_scroll_time = Rc::new(Cell::new(0.0));
         let last_resize_time = Rc::new(Cell::new(0.0));
+        let animation_frame = Rc::new(Cell::new(None::<AnimationFrameRequestHandle>));
 
-        // Pre-calculate values for hot path
-        let throttle_ms = config.throttle_ms as f64;
-        let resize_debounce_ms = config.resize_debounce_ms as f64;
+        // Pre-calculate values for hot path, bar the throttle which `set_throttle_ms` can change
+        let throttle_ms = Rc::new(Cell::new(config.throttle_ms as f64));
         let offset_top = config.offset_top;
         let offset_bottom = config.offset_bottom;
+        let min_scrollable_px = config.min_scrollable_px;
+        let smoother = config
+            .smoothing
+            .filter(|factor| *factor > 0.0 && *factor < 1.0)
+            .map(|factor| {
+                let callbacks = callbacks.clone();
+                let dispatch = move |progress: ScrollProgress| callbacks.dispatch(progress);
+                ProgressSmoother::new(Rc::new(dispatch), factor)
+            });
 
         // Create scroll closure with optimized hot path
         let element_clone = element.clone();
+        let scroll_tracking = tracking.clone();
         let callbacks_clone = callbacks.clone();
-        let last_scroll_time_clone = last_scroll_time.clone();
-
-        let window = web_sys::window().unwrap();
-        let body = window.document().unwrap().body().unwrap();
-        let performance = window.performance().unwrap();
-
-        // Initial values for full page calculation
-        let current_window_height = window
-            .visual_viewport()
-            .map(|viewport| viewport.height())
-            .unwrap_or_else(|| window.inner_height().unwrap().unchecked_into_f64());
-        let current_body_height = body.client_height();
+        let scroll_smoother = smoother.clone();
 
-        let scroll_closure = Closure::wrap(Box::new(move || {
-            let now = performance.now();
+        let dispatch_scroll = move || {
+            // Inline progress calculation
+            let previous_progress = last_progress.get_untracked();
+            let mut new_progress = match &scroll_tracking {
+                Tracking::Container => {
+                    let mut new_progress = previous_progress;
+                    new_progress.scroll_y = element_clone.scroll_top() as f64
+                        + offset_top.resolve(element_clone.client_height() as f64);
+                    new_progress.calculate_progress();
+                    new_progress
+                }
+                // The element moves against the viewport rather than within a measured range
+                Tracking::Viewport(target) => {
+                    measure_viewport_progress(target, offset_top, offset_bottom)
+                }
+            };
+            let is_active = hold_unscrollable(&mut new_progress, min_scrollable_px);
 
-            // Inline throttling check
-            let last_time = last_scroll_time_clone.get();
-            if now - last_time < throttle_ms {
+            // e.g. a trailing dispatch landing on the position the leading one already reported
+            if new_progress.approx_eq(&previous_progress, UNCHANGED_EPSILON) {
                 return;
             }
-            last_scroll_time_clone.set(now);
-
-            // Inline progress calculation
-            let mut new_progress = last_progress.get_untracked();
-
-            new_progress.scroll_y = element_clone.scroll_top() as f64 + offset_top;
-
-            new_progress.calculate_progress();
 
             last_progress.set(new_progress);
+            if !is_active {
+                return;
+            }
 
-            // Execute callbacks with minimal overhead
-            for callback in callbacks_clone.borrow().iter() {
-                callback(new_progress);
+            if let Some(smoother) = &scroll_smoother {
+                smoother.retarget(previous_progress, new_progress);
+                return;
             }
-        }) as Box<dyn FnMut()>);
+
+            // Execute callbacks with minimal overhead
+            callbacks_clone.dispatch(new_progress);
+        };
 
         // Create resize closure
         let resize_element = element.clone();
+        let resize_tracking = tracking.clone();
         let resize_callbacks = callbacks.clone();
-        let last_resize_time_clone = last_resize_time.clone();
+        let resize_smoother = smoother.clone();
+        let on_resize = move || {
+            let mut progress =
+                measure_tracked(&resize_element, &resize_tracking, offset_top, offset_bottom);
+            let is_active = hold_unscrollable(&mut progress, min_scrollable_px);
 
-        let window = web_sys::window().unwrap();
-        let performance = window.performance().unwrap();
+            last_progress.set(progress);
+            set_if_changed(is_scrollable, progress.is_scrollable());
+            if !is_active {
+                return;
+            }
 
-        // Attach event listeners
-        element
-            .add_event_listener_with_callback("scroll", scroll_closure.as_ref().unchecked_ref())?;
+            if let Some(smoother) = &resize_smoother {
+                smoother.snap(progress);
+            }
+            resize_callbacks.dispatch(progress);
+        };
 
-        let handle = window_event_listener(resize, move |_| {
-            let now = performance.now();
+        listen_for_scroll_and_resize(
+            &scroll_target,
+            &config,
+            throttle_ms.clone(),
+            last_scroll_time.clone(),
+            last_resize_time.clone(),
+            dispatch_scroll,
+            on_resize,
+        )?;
 
-            let last_time = last_resize_time_clone.get();
-            if now - last_time < resize_debounce_ms {
-                return;
+        // Passive since these never preventDefault, which keeps scrolling off the main thread
+        let listener_options = AddEventListenerOptions::new();
+        listener_options.set_passive(true);
+
+        // Any input from the user takes over from an in-flight `animated_scroll_to_progress`.
+        // Scroll itself can't be used as the animation fires its own scroll events
+        let interrupt_frame = animation_frame.clone();
+        let interrupt_closure = Closure::wrap(Box::new(move || {
+            if let Some(frame) = interrupt_frame.take() {
+                frame.cancel();
             }
-            last_resize_time_clone.set(now);
+        }) as Box<dyn FnMut()>);
+        for event in ANIMATION_INTERRUPT_EVENTS {
+            scroll_target.add_event_listener_with_callback_and_add_event_listener_options(
+                event,
+                interrupt_closure.as_ref().unchecked_ref(),
+                &listener_options,
+            )?;
+        }
 
-            let scroll_y = resize_element.scroll_top() as f64 + offset_top;
-            let scroll_height = resize_element.scroll_height() as f64;
-            let mut viewport_height =
-                resize_element.client_height() as f64 - offset_top - offset_bottom;
+        // Content changing size on its own can start or stop it overflowing with neither a scroll
+        // nor a resize
+        let overflow_element = element.clone();
+        let overflow_tracking = tracking.clone();
+        let content_observer = ContentSizeObserver::new(&element, move || {
+            let progress = measure_tracked(
+                &overflow_element,
+                &overflow_tracking,
+                offset_top,
+                offset_bottom,
+            );
+            set_if_changed(is_scrollable, progress.is_scrollable());
+        })?;
 
-            if viewport_height as i32 == body.client_height() {
-                viewport_height -= viewport_height
-                    - window
-                        .visual_viewport()
-                        .map(|viewport| viewport.height())
-                        .unwrap_or_else(|| window.inner_height().unwrap().unchecked_into_f64());
-            }
+        // The scroll callbacks holding the senders live until the listeners go, so end the
+        // streams along with the listeners rather than waiting on those to drop
+        let stream_senders: Rc<std::cell::RefCell<Vec<UnboundedSender<ScrollProgress>>>> =
+            Rc::default();
 
-            let progress = ScrollProgress::new(scroll_y, scroll_height, viewport_height);
-            last_progress.set(progress);
+        let cleanup_stream_senders = stream_senders.clone();
+        let cleanup_smoother = smoother.clone();
+        on_cleanup_local(move || {
+            content_observer.disconnect();
 
-            for callback in resize_callbacks.borrow().iter() {
-                callback(progress);
+            for sender in cleanup_stream_senders.borrow().iter() {
+                sender.close_channel();
             }
-        });
 
-        // store the values in the reactive system which stops them from being dropped immediately
-        // attaches their lifetime to the lifetime of the reactive context they're in
-        let _ = StoredValue::new_local(scroll_closure);
+            if let Some(smoother) = &cleanup_smoother {
+                smoother.cancel();
+            }
 
-        on_cleanup(move || {
-            handle.remove();
+            for event in ANIMATION_INTERRUPT_EVENTS {
+                let _ = scroll_target.remove_event_listener_with_callback(
+                    event,
+                    interrupt_closure.as_ref().unchecked_ref(),
+                );
+            }
         });
 
         // Calculate initial progress
-        let scroll_y = element.scroll_top() as f64 + config.offset_top;
-        let scroll_height = element.scroll_height() as f64;
-        let mut viewport_height =
-            element.client_height() as f64 - config.offset_top - config.offset_bottom;
-
-        if viewport_height as i32 == current_body_height {
-            viewport_height -= viewport_height - current_window_height;
-        }
-
-        let initial_progress = ScrollProgress::new(scroll_y, scroll_height, viewport_height);
+        let mut initial_progress =
+            measure_tracked(&element, &tracking, config.offset_top, config.offset_bottom);
+        let is_active = hold_unscrollable(&mut initial_progress, config.min_scrollable_px);
 
         last_progress.set(initial_progress);
+        is_scrollable.set(initial_progress.is_scrollable());
 
         // Handle run_straight_away for non-scrollable content
-        let is_not_scrollable = scroll_height <= viewport_height;
-        if config.run_straight_away || is_not_scrollable {
+        if config.run_straight_away || (is_active && !initial_progress.is_scrollable()) {
             let callbacks_clone = callbacks.clone();
             request_animation_frame(move || {
-                for callback in callbacks_clone.borrow().iter() {
-                    callback(initial_progress);
-                }
+                callbacks_clone.dispatch(initial_progress);
             });
         }
 
         Ok(Self {
+            id: STORYTELLER_ID.fetch_add(1, Ordering::Relaxed),
             element,
+            tracking,
             config,
             last_progress,
+            is_scrollable,
             callbacks,
+            registering_tag: std::cell::RefCell::new(None),
             last_scroll_time,
             last_resize_time,
+            animation_frame,
+            smoother,
+            throttle_ms,
+            stream_senders,
+            named_ranges: std::cell::RefCell::new(Vec::new()),
         })
     }
 
-    pub fn for_window(config: Option<ScrollStorytellerConfig>) -> Result<Self, JsValue> {
-        let window = web_sys::window().ok_or("No window")?;
-        let document = window.document().ok_or("No document")?;
-        let element = document
-            .document_element()
-            .ok_or("No document element")?
-            // This may just break. Does the document element allow casting to html element?
-            .unchecked_into::<HtmlElement>();
-        Self::new(element, config)
+    pub fn for_window(config: Option<ScrollStorytellerConfig>) -> Result<Self, ScrollError> {
+        let window = web_sys::window().ok_or(ScrollError::NoWindow)?;
+        let element = Self::page_scrolling_element()?;
+
+        // Page scroll events are dispatched to the window rather than the scrolling element
+        Ok(Self::with_scroll_target(
+            element,
+            window.into(),
+            Tracking::Container,
+            config,
+        )?)
     }
 
-    #[inline(always)]
-    pub fn on_scroll<F>(&self, callback: F)
+    /// Tracks `element` through the window's viewport instead of a container's scroll range:
+    /// 0.0 when its top meets the viewport's bottom edge, 0.5 as its center crosses the middle
+    /// and 1.0 once its bottom passes the top edge. Offsets inset the viewport's edges. The
+    /// programmatic scrolling methods scroll the page, and `animate_css_property` always runs
+    /// on scroll callbacks
+    pub fn for_element_in_viewport(
+        element: impl Into<Element>,
+        config: Option<ScrollStorytellerConfig>,
+    ) -> Result<Self, ScrollError> {
+        let page = Self::page_scrolling_element()?;
+        let window = web_sys::window().ok_or(ScrollError::NoWindow)?;
+
+        Ok(Self::with_scroll_target(
+            page,
+            window.into(),
+            Tracking::Viewport(element.into()),
+            config,
+        )?)
+    }
+
+    /// The element holding the page's scroll position, which browsers disagree on
+    fn page_scrolling_element() -> Result<Element, ScrollError> {
+        let document = web_sys::window()
+            .ok_or(ScrollError::NoWindow)?
+            .document()
+            .ok_or(ScrollError::NoDocument)?;
+
+        document
+            .scrolling_element()
+            .or_else(|| document.document_element())
+            .or_else(|| document.body().map(Element::from))
+            .ok_or(ScrollError::NoScrollingElement)
+    }
+
+    #[inline(always)]
+    pub fn on_scroll<F>(&self, callback: F)
     where
         F: Fn(ScrollProgress) + 'static,
     {
-        self.callbacks.borrow_mut().push(Box::new(callback));
+        let tag = self.registering_tag.borrow().clone();
+        self.callbacks.push(tag, Box::new(callback));
+    }
+
+    /// Like `on_scroll`, but removable with `remove_tagged(tag)`
+    pub fn on_scroll_tagged<F>(&self, tag: &str, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        self.tagged(tag, |storyteller| storyteller.on_scroll(callback));
+    }
+
+    /// Tags every callback `register` adds, through any `on_*` method, so an effect made of
+    /// several callbacks can be torn down as one with `remove_tagged(tag)`
+    pub fn tagged<F>(&self, tag: &str, register: F)
+    where
+        F: FnOnce(&Self),
+    {
+        let previous = self.registering_tag.replace(Some(Rc::from(tag)));
+        register(self);
+        self.registering_tag.replace(previous);
+    }
+
+    /// Removes every callback registered under `tag`. Listeners some methods add alongside their
+    /// callback (e.g. `enable_drag_scroll`) stay until unmount. Called from inside a scroll
+    /// callback, the removal takes effect once the current dispatch has finished
+    pub fn remove_tagged(&self, tag: &str) {
+        self.callbacks.remove_tagged(tag);
+        self.named_ranges
+            .borrow_mut()
+            .retain(|range| range.tag.as_deref() != Some(tag));
+    }
+
+    /// Like `on_scroll`, but also passes this storyteller's `id`, so one handler shared across
+    /// several storytellers can tell which one scrolled
+    pub fn on_scroll_with_source<F>(&self, callback: F)
+    where
+        F: Fn(usize, ScrollProgress) + 'static,
+    {
+        let id = self.id;
+        self.on_scroll(move |progress| callback(id, progress));
+    }
+
+    /// Like `on_scroll`, but also passes the pixels scrolled since the previous event (positive
+    /// going down), e.g. to accumulate scroll energy. Reports 0.0 when progress changed because
+    /// of a resize rather than the user scrolling
+    pub fn on_scroll_delta<F>(&self, callback: F)
+    where
+        F: Fn(ScrollProgress, f64) + 'static,
+    {
+        let previous = Cell::new(self.last_progress.get_untracked());
+
+        self.on_scroll(move |progress| {
+            let delta = progress.scroll_delta(&previous.replace(progress));
+            callback(progress, delta);
+        });
+    }
+
+    /// Every dispatched progress as a `Stream`, for `while let Some(progress) = stream.next().await`
+    /// inside `spawn_local`. Values queue up while the consumer is busy, and the stream ends
+    /// when the storyteller's listeners are cleaned up with the reactive owner it was made in
+    pub fn progress_stream(&self) -> impl Stream<Item = ScrollProgress> + use<> {
+        let (sender, receiver) = unbounded();
+        self.stream_senders.borrow_mut().push(sender.clone());
+
+        // Errors once the stream is dropped or the storyteller closed it, either way nobody's
+        // listening anymore
+        self.on_scroll(move |progress| {
+            let _ = sender.unbounded_send(progress);
+        });
+
+        receiver
+    }
+
+    /// Identifies this storyteller, unique among those created on the page
+    #[inline(always)]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Changes the throttle (or debounce) window from the next scroll event on, e.g. to 0 while
+    /// the user drags a scrubber. Pass the config's `throttle_ms` again to restore it
+    pub fn set_throttle_ms(&self, ms: u32) {
+        self.throttle_ms.set(ms as f64);
+    }
+
+    /// Like `on_scroll`, but this callback runs at most once every `throttle_ms` on top of the
+    /// global `throttle_ms` gate, for expensive work alongside cheap per-frame callbacks
+    pub fn on_scroll_throttled<F>(&self, throttle_ms: u32, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        self.on_scroll(throttle(throttle_ms, callback));
+    }
+
+    /// Calls `callback` with the final progress once scrolling has stopped for `idle_ms`, exactly
+    /// once per pause, e.g. for "user paused here" analytics
+    pub fn on_scroll_idle<F>(&self, idle_ms: u32, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        self.on_scroll(debounce(idle_ms, callback));
+    }
+
+    /// Like `on_scroll`, but skips events where progress moved less than `epsilon` since the
+    /// last dispatch (e.g. overscroll bounce). `DEFAULT_PROGRESS_EPSILON` (0.001) suits most uses
+    pub fn on_progress_change<F>(&self, epsilon: f64, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        // NaN never compares within epsilon, so the first event always dispatches
+        let last_dispatched = Rc::new(Cell::new(f64::NAN));
+
+        self.on_scroll(move |progress| {
+            if (progress.progress - last_dispatched.get()).abs() < epsilon {
+                return;
+            }
+            last_dispatched.set(progress.progress);
+            callback(progress);
+        });
     }
 
     pub fn on_progress_range<F>(&self, from: f64, to: f64, callback: F)
@@ -305,123 +1096,2424 @@ impl ScrollStoryteller {
         });
     }
 
+    /// Like `on_progress_range`, but remembered under `name` for `active_ranges`
+    pub fn on_named_progress_range<F>(&self, name: &str, from: f64, to: f64, callback: F)
+    where
+        F: Fn(ScrollProgress, f64) + 'static,
+    {
+        self.named_ranges.borrow_mut().push(NamedRange {
+            tag: self.registering_tag.borrow().clone(),
+            name: name.to_owned(),
+            from,
+            to,
+        });
+        self.on_progress_range(from, to, callback);
+    }
+
+    /// Names of the `on_named_progress_range` ranges the current progress is inside, in the
+    /// order they were registered, e.g. for a debug overlay or a current-section label
+    pub fn active_ranges(&self) -> Vec<String> {
+        active_range_names(
+            &self.named_ranges.borrow(),
+            &self.last_progress.get_untracked(),
+        )
+    }
+
+    /// Calls `callback` when progress moves into `[from, to]`, including jumping straight over it
     pub fn on_enter_range<F>(&self, from: f64, to: f64, callback: F)
     where
         F: Fn(ScrollProgress) + 'static,
     {
-        let was_in_range = Rc::new(Cell::new(None::<bool>));
+        let tracker = Rc::new(std::cell::RefCell::new(RangeTracker::new(from, to)));
 
         // Handle run_straight_away
         if self.config.run_straight_away {
             let current_progress = self.last_progress.get_untracked();
-            let is_currently_in_range = current_progress.is_in_range(from, to);
-            if is_currently_in_range {
+            let event = tracker.borrow_mut().update(current_progress.progress);
+            if event.is_some_and(RangeEvent::is_enter) {
                 callback(current_progress);
-                was_in_range.set(Some(true));
-            } else {
-                was_in_range.set(Some(false));
             }
         }
 
         self.on_scroll(move |progress| {
-            let is_in_range = progress.is_in_range(from, to);
-            match was_in_range.get() {
-                None => {
-                    if is_in_range {
-                        callback(progress);
-                    }
-                    was_in_range.set(Some(is_in_range));
-                }
-                Some(was_in_range_val) => {
-                    if is_in_range && !was_in_range_val {
-                        callback(progress);
-                    }
-                    was_in_range.set(Some(is_in_range));
-                }
+            let event = tracker.borrow_mut().update(progress.progress);
+            if event.is_some_and(RangeEvent::is_enter) {
+                callback(progress);
+            }
+        });
+    }
+
+    /// Like `on_enter_range`, but only when the range is entered by scrolling down, e.g. to reveal
+    /// something once on the way through
+    pub fn on_enter_range_downward<F>(&self, from: f64, to: f64, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        self.on_directional_enter(from, to, ScrollDirection::Down, callback);
+    }
+
+    /// Like `on_enter_range`, but only when the range is entered by scrolling up
+    pub fn on_enter_range_upward<F>(&self, from: f64, to: f64, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        self.on_directional_enter(from, to, ScrollDirection::Up, callback);
+    }
+
+    fn on_directional_enter<F>(&self, from: f64, to: f64, direction: ScrollDirection, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        let tracker = Rc::new(std::cell::RefCell::new(RangeTracker::new(from, to)));
+        let current_progress = self.last_progress.get_untracked();
+        let previous = Cell::new(current_progress);
+
+        // Starting in range has no direction, so it never counts as an entry
+        if self.config.run_straight_away {
+            tracker.borrow_mut().update(current_progress.progress);
+        }
+
+        self.on_scroll(move |progress| {
+            let event = tracker.borrow_mut().update(progress.progress);
+            let moved = ScrollDirection::between(&previous.replace(progress), &progress);
+            if event.is_some_and(RangeEvent::is_enter) && moved == Some(direction) {
+                callback(progress);
+            }
+        });
+    }
+
+    /// Calls `callback` each time progress crosses `point` in `direction`, e.g. to start a video
+    /// once the reader scrolls past 40% going down. Crossing back the other way re-arms it
+    pub fn on_cross<F>(&self, point: f64, direction: ScrollDirection, callback: F)
+    where
+        F: Fn(ScrollProgress) + 'static,
+    {
+        let tracker = Rc::new(std::cell::RefCell::new(CrossingTracker::new(
+            point, direction,
+        )));
+        tracker
+            .borrow_mut()
+            .update(self.last_progress.get_untracked().progress);
+
+        self.on_scroll(move |progress| {
+            if tracker.borrow_mut().update(progress.progress) {
+                callback(progress);
             }
         });
     }
 
+    /// Calls `callback` when progress leaves `[from, to]`, including jumping straight over it
     pub fn on_exit_range<F>(&self, from: f64, to: f64, callback: F)
     where
         F: Fn(ScrollProgress) + 'static,
     {
-        let was_in_range = Rc::new(Cell::new(None::<bool>));
+        let tracker = Rc::new(std::cell::RefCell::new(RangeTracker::new(from, to)));
 
         if self.config.run_straight_away {
             let current_progress = self.last_progress.get_untracked();
-            let is_currently_in_range = current_progress.is_in_range(from, to);
-            was_in_range.set(Some(is_currently_in_range));
+            tracker.borrow_mut().update(current_progress.progress);
         }
 
         self.on_scroll(move |progress| {
-            let is_in_range = progress.is_in_range(from, to);
-            match was_in_range.get() {
-                None => was_in_range.set(Some(is_in_range)),
-                Some(was_in_range_val) => {
-                    if !is_in_range && was_in_range_val {
-                        callback(progress);
-                    }
-                    was_in_range.set(Some(is_in_range));
-                }
+            let event = tracker.borrow_mut().update(progress.progress);
+            if event.is_some_and(RangeEvent::is_exit) {
+                callback(progress);
+            }
+        });
+    }
+
+    /// Pins `el` to the top of the scroll container while progress is within `[from, to]`.
+    /// When released past `to` the element stays at the bottom of the pin zone rather than
+    /// snapping back to its flow position.
+    pub fn pin_element(&self, el: &HtmlElement, from: f64, to: f64) {
+        let pinned = el.clone();
+        self.on_enter_range(from, to, move |_progress| {
+            let style = pinned.style();
+            let _ = style.set_property("position", "sticky");
+            let _ = style.set_property("top", "0px");
+        });
+
+        let released = el.clone();
+        self.on_exit_range(from, to, move |progress| {
+            let style = released.style();
+            if progress.progress > to {
+                let pin_distance = (to - from) * progress.max_scroll();
+                let _ = style.set_property("position", "relative");
+                let _ = style.set_property("top", &format!("{}px", pin_distance));
+            } else {
+                let _ = style.remove_property("position");
+                let _ = style.remove_property("top");
             }
         });
     }
 
+    /// Calls `callback` on every scroll with `child`'s progress through the visible area of the
+    /// container: 0.0 while its top is still below the bottom edge, 1.0 once its bottom has passed
+    /// the top edge, for "animate as this passes through the screen" effects
+    pub fn on_element_progress<F>(&self, child: &HtmlElement, callback: F)
+    where
+        F: Fn(f64) + 'static,
+    {
+        let container = self.element.clone();
+        let child = child.clone();
+        self.on_scroll(move |_| {
+            let (viewport_top, viewport_height) = visible_area(&container);
+            let rect = child.get_bounding_client_rect();
+            callback(element_progress(
+                rect.top(),
+                rect.height(),
+                viewport_top,
+                viewport_height,
+            ));
+        });
+    }
+
+    /// Writes the current progress into the CSS custom property `name` on `el` on every scroll,
+    /// e.g. `bind_css_var(&el, "--scroll")` for use with `calc(var(--scroll) * -100px)`
+    pub fn bind_css_var(&self, el: &HtmlElement, name: &str) {
+        let element = el.clone();
+        let name = name.to_string();
+        self.on_scroll(move |progress| {
+            let _ = element
+                .style()
+                .set_property(&name, &progress.progress.to_string());
+        });
+    }
+
+    /// Animates `property` on `el` from `value(from)` to `value(to)` over the whole scroll, e.g.
+    /// `animate_css_property(&el, "transform", 0.0, -100.0, |y| format!("translateY({}px)", y))`.
+    ///
+    /// When `supports_scroll_timeline()` and `config.css_scroll_timeline` allow, this injects a
+    /// scroll-driven CSS animation that runs off the main thread, with `el`'s nearest scrolling
+    /// ancestor as the timeline (so `el` must be inside the container, and offsets don't apply).
+    /// Otherwise the property is updated from `on_scroll`
+    pub fn animate_css_property<F>(
+        &self,
+        el: &HtmlElement,
+        property: &str,
+        from: f64,
+        to: f64,
+        value: F,
+    ) -> Result<(), JsValue>
+    where
+        F: Fn(f64) -> String + 'static,
+    {
+        // The injected animation follows the nearest scroll container, not an element's passage
+        // through the viewport
+        let follows_container = matches!(self.tracking, Tracking::Container);
+        if self.config.css_scroll_timeline && follows_container && supports_scroll_timeline() {
+            return inject_scroll_animation(el, property, &value(from), &value(to));
+        }
+
+        let element = el.clone();
+        let property = property.to_string();
+        let apply = move |progress: ScrollProgress| {
+            let _ = element
+                .style()
+                .set_property(&property, &value(from + (to - from) * progress.progress));
+        };
+
+        apply(self.last_progress.get_untracked());
+        self.on_scroll(apply);
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn progress(&self) -> ReadSignal<ScrollProgress> {
         self.last_progress.read_only()
     }
 
-    pub fn scroll_to_progress(&self, progress: f64) -> Result<(), JsValue> {
-        let clamped_progress = progress.clamp(0.0, 1.0);
-        let current = self.last_progress.get_untracked();
-        let max_scroll = (current.scroll_height - current.viewport_height).max(0.0);
-        let target_scroll = (clamped_progress * max_scroll) - self.config.offset_top;
+    /// Whether the content overflows the container, kept up to date on resize, on the container
+    /// or its children changing size and on `refresh`. Only changes when the answer does, e.g. to
+    /// show a "scroll for more" hint
+    #[inline(always)]
+    pub fn is_scrollable(&self) -> ReadSignal<bool> {
+        self.is_scrollable.read_only()
+    }
 
-        let options = ScrollToOptions::new();
-        options.set_top(target_scroll);
-        if self.config.smooth_scroll {
-            options.set_behavior(ScrollBehavior::Smooth);
+    /// Scrollable distance in px as of the last scroll or resize
+    #[inline(always)]
+    pub fn max_scroll(&self) -> f64 {
+        self.last_progress.get_untracked().max_scroll()
+    }
+
+    /// Re-measures the container and runs every callback with the fresh progress, for when
+    /// content changed size (e.g. after an animation or a DOM update) without a scroll or resize
+    pub fn refresh(&self) {
+        let mut progress = measure_tracked(
+            &self.element,
+            &self.tracking,
+            self.config.offset_top,
+            self.config.offset_bottom,
+        );
+        let is_active = hold_unscrollable(&mut progress, self.config.min_scrollable_px);
+
+        self.last_progress.set(progress);
+        set_if_changed(self.is_scrollable, progress.is_scrollable());
+        if !is_active {
+            return;
         }
 
-        self.element.scroll_with_scroll_to_options(&options);
+        if let Some(smoother) = &self.smoother {
+            smoother.snap(progress);
+        }
+        self.callbacks.dispatch(progress);
+    }
+
+    pub fn scroll_to_progress(&self, progress: f64) -> Result<(), JsValue> {
+        let target_scroll = self.progress_to_pixels(progress);
+
+        scroll_element_to(&self.element, target_scroll, self.config.smooth_scroll);
         Ok(())
     }
 
+    /// Progress the container would report at `scrollTop` `px`, using the current measurements
+    /// and `offset_top`. The inverse of `progress_to_pixels`
+    pub fn pixels_to_progress(&self, px: f64) -> f64 {
+        if let Tracking::Viewport(target) = &self.tracking {
+            let now = measure_viewport_progress(
+                target,
+                self.config.offset_top,
+                self.config.offset_bottom,
+            );
+            let scroll_y = now.scroll_y + px - self.element.scroll_top() as f64;
+            return scroll_top_to_progress(scroll_y, 0.0, now.max_scroll());
+        }
+
+        scroll_top_to_progress(
+            px,
+            self.config
+                .offset_top
+                .resolve(self.element.client_height() as f64),
+            self.max_scroll(),
+        )
+    }
+
+    /// The `scrollTop` in px that `scroll_to_progress` would scroll to for `progress`
+    pub fn progress_to_pixels(&self, progress: f64) -> f64 {
+        progress_scroll_top(
+            &self.element,
+            &self.tracking,
+            self.last_progress.get_untracked(),
+            (self.config.offset_top, self.config.offset_bottom),
+            progress,
+        )
+    }
+
     pub fn scroll_to_pixels(&self, pixels: f64) -> Result<(), JsValue> {
-        let options = ScrollToOptions::new();
-        options.set_top(pixels);
-        if self.config.smooth_scroll {
-            options.set_behavior(ScrollBehavior::Smooth);
+        scroll_element_to(&self.element, pixels, self.config.smooth_scroll);
+        Ok(())
+    }
+
+    /// Lets the container be scrolled from the keyboard while it has focus: PageUp/PageDown by
+    /// a viewport, Home/End to the start/end and the arrow keys in small steps. Makes the
+    /// container focusable if it isn't already. Keys pressed in form fields or editable
+    /// content, with Ctrl/Alt/Meta held, or for a container on anything but the container
+    /// itself are left to the browser. The listener is removed when the calling component
+    /// unmounts
+    pub fn enable_keyboard(&self) -> Result<(), JsValue> {
+        if !self.element.has_attribute("tabindex") {
+            self.element.set_attribute("tabindex", "0")?;
         }
 
-        self.element.scroll_with_scroll_to_options(&options);
+        // Every keydown on the page bubbles up to the page's scrolling element, a container only
+        // handles its own
+        let is_page = matches!(self.tracking, Tracking::Viewport(_))
+            || Self::page_scrolling_element().is_ok_and(|page| page == self.element);
+        let container = EventTarget::from(self.element.clone());
+        let element = self.element.clone();
+        let tracking = self.tracking.clone();
+        let last_progress = self.last_progress;
+        let offsets = (self.config.offset_top, self.config.offset_bottom);
+        let smooth_scroll = self.config.smooth_scroll;
+
+        let keydown = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+            if event.ctrl_key() || event.alt_key() || event.meta_key() {
+                return;
+            }
+
+            let target = event.target();
+            if is_editable_target(target.as_ref())
+                || (!is_page && target.as_ref() != Some(&container))
+            {
+                return;
+            }
+
+            let current = last_progress.get_untracked();
+            let Some(action) = KeyboardScroll::from_key(&event.key(), current.viewport_height)
+            else {
+                return;
+            };
+
+            // Stop the browser scrolling natively as well
+            event.prevent_default();
+
+            let target_scroll = match action {
+                KeyboardScroll::By(delta) => element.scroll_top() as f64 + delta,
+                KeyboardScroll::ToProgress(progress) => {
+                    progress_scroll_top(&element, &tracking, current, offsets, progress)
+                }
+            };
+            scroll_element_to(&element, target_scroll, smooth_scroll);
+        }) as Box<dyn FnMut(KeyboardEvent)>);
+
+        self.element
+            .add_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref())?;
+
+        let element = self.element.clone();
+        on_cleanup_local(move || {
+            let _ = element
+                .remove_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref());
+        });
+
         Ok(())
     }
-}
 
-#[inline(always)]
-pub fn create_window_storyteller() -> Result<ScrollStoryteller, JsValue> {
-    ScrollStoryteller::for_window(None)
+    /// Lets the container be scrolled by clicking and dragging with a mouse, e.g. for horizontal
+    /// galleries. Touch and pens are left to native scrolling, as are presses on form fields,
+    /// buttons and editable content. Progress callbacks fire through the usual scroll listener as
+    /// the drag moves the container, and a drag that moved doesn't count as a click on whatever
+    /// it ends over
+    pub fn enable_drag_scroll(&self) -> Result<(), JsValue> {
+        let element = self.element.clone();
+        let drag = Rc::new(Cell::new(None::<DragScroll>));
+        let suppress_click = Rc::new(Cell::new(false));
+        set_cursor(&self.element, Some("grab"))?;
+
+        let drag_suppress_click = suppress_click.clone();
+        let on_pointer = Closure::wrap(Box::new(move |event: PointerEvent| {
+            let (x, y) = (event.client_x() as f64, event.client_y() as f64);
+
+            match event.type_().as_str() {
+                "pointerdown" => {
+                    if event.pointer_type() != "mouse" || event.button() != 0 {
+                        return;
+                    }
+                    if closest_match(event.target().as_ref(), DRAG_IGNORE_SELECTOR) {
+                        return;
+                    }
+
+                    // Default isn't prevented yet so a press that turns out to be a click still
+                    // focuses and selects as usual
+                    drag.set(Some(DragScroll::new(event.pointer_id(), x, y)));
+                    drag_suppress_click.set(false);
+                    let _ = set_cursor(&element, Some("grabbing"));
+                }
+                "pointermove" => {
+                    let Some(mut state) = drag
+                        .get()
+                        .filter(|state| state.pointer_id == event.pointer_id())
+                    else {
+                        return;
+                    };
+                    let was_click = state.is_click();
+                    let (dx, dy) = state.move_to(x, y);
+                    drag.set(Some(state));
+
+                    if !state.is_click() {
+                        // Keeps the moves coming while the pointer is outside the container
+                        if was_click {
+                            let _ = element.set_pointer_capture(state.pointer_id);
+                        }
+                        // Stops text selection and native image dragging following the drag
+                        event.prevent_default();
+                    }
+
+                    // Content follows the pointer, so scroll against the drag
+                    let options = ScrollToOptions::new();
+                    options.set_left(-dx);
+                    options.set_top(-dy);
+                    options.set_behavior(ScrollBehavior::Instant);
+                    element.scroll_by_with_scroll_to_options(&options);
+                }
+                // pointerup, pointercancel and pointerleave all end the drag
+                _ => {
+                    let Some(state) = drag
+                        .get()
+                        .filter(|state| state.pointer_id == event.pointer_id())
+                    else {
+                        return;
+                    };
+                    drag.set(None);
+
+                    let _ = element.release_pointer_capture(state.pointer_id);
+                    drag_suppress_click.set(!state.is_click());
+                    let _ = set_cursor(&element, Some("grab"));
+                }
+            }
+        }) as Box<dyn FnMut(PointerEvent)>);
+
+        let on_click = Closure::wrap(Box::new(move |event: Event| {
+            if suppress_click.take() {
+                event.prevent_default();
+                event.stop_propagation();
+            }
+        }) as Box<dyn FnMut(Event)>);
+
+        for event in DRAG_SCROLL_EVENTS {
+            self.element
+                .add_event_listener_with_callback(event, on_pointer.as_ref().unchecked_ref())?;
+        }
+        // Capture phase so the click is stopped before it reaches the element under the pointer
+        self.element.add_event_listener_with_callback_and_bool(
+            "click",
+            on_click.as_ref().unchecked_ref(),
+            true,
+        )?;
+
+        let element = self.element.clone();
+        on_cleanup_local(move || {
+            for event in DRAG_SCROLL_EVENTS {
+                let _ = element.remove_event_listener_with_callback(
+                    event,
+                    on_pointer.as_ref().unchecked_ref(),
+                );
+            }
+            let _ = element.remove_event_listener_with_callback_and_bool(
+                "click",
+                on_click.as_ref().unchecked_ref(),
+                true,
+            );
+            let _ = set_cursor(&element, None);
+        });
+
+        Ok(())
+    }
+
+    /// Calls `callback` with each wheel event's vertical delta in pixels, whether the device
+    /// reports pixels, lines or pages. Runs before the browser scrolls, unlike `on_scroll`, for
+    /// custom wheel handling alongside the storyteller
+    pub fn on_wheel_normalized<F>(&self, callback: F) -> Result<(), JsValue>
+    where
+        F: Fn(f64) + 'static,
+    {
+        let element = self.element.clone();
+        let on_wheel = Closure::wrap(Box::new(move |event: WheelEvent| {
+            callback(normalize_wheel_delta(
+                event.delta_y(),
+                event.delta_mode(),
+                element.client_height() as f64,
+            ));
+        }) as Box<dyn FnMut(WheelEvent)>);
+
+        let options = AddEventListenerOptions::new();
+        options.set_passive(true);
+        self.element
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                "wheel",
+                on_wheel.as_ref().unchecked_ref(),
+                &options,
+            )?;
+
+        let element = self.element.clone();
+        on_cleanup_local(move || {
+            let _ = element
+                .remove_event_listener_with_callback("wheel", on_wheel.as_ref().unchecked_ref());
+        });
+
+        Ok(())
+    }
+
+    /// Stops an in-flight `animated_scroll_to_progress` where it is, a no-op if none is running
+    pub fn cancel_scroll_animation(&self) {
+        if let Some(frame) = self.animation_frame.take() {
+            frame.cancel();
+        }
+    }
+
+    /// Scrolls to `progress` over exactly `duration_ms`, moving the container each animation frame
+    /// along `easing` instead of leaving the timing to the browser's smooth scroll. Starting
+    /// another animation, or the user scrolling themselves, cancels any that's still in flight
+    pub fn animated_scroll_to_progress(
+        &self,
+        progress: f64,
+        duration_ms: u32,
+        easing: EasingFunction,
+    ) -> Result<(), JsValue> {
+        let performance = web_sys::window()
+            .and_then(|window| window.performance())
+            .ok_or_else(|| JsValue::from_str("Performance API unavailable"))?;
+
+        let target_scroll = self.progress_to_pixels(progress);
+
+        self.cancel_scroll_animation();
+
+        ScrollAnimation {
+            element: self.element.clone(),
+            start_time: performance.now(),
+            performance,
+            pending_frame: self.animation_frame.clone(),
+            from: self.element.scroll_top() as f64,
+            to: target_scroll,
+            duration_ms: duration_ms as f64,
+            easing,
+        }
+        .next_frame();
+
+        Ok(())
+    }
+
+    /// Saves the container's scroll position to `sessionStorage` under `key`, debounced so a
+    /// write only happens once scrolling settles
+    pub fn persist_scroll_position(&self, key: &str) {
+        let element = self.element.clone();
+        let key = key.to_string();
+
+        self.on_scroll(debounce(
+            PERSIST_SCROLL_DEBOUNCE_MS,
+            move |_: ScrollProgress| {
+                if let Some(storage) = session_storage() {
+                    let _ = storage.set_item(&key, &element.scroll_top().to_string());
+                }
+            },
+        ));
+    }
+
+    /// Scrolls to the position saved by `persist_scroll_position` under `key`, clamped to the
+    /// current scroll range in case the content has shrunk since
+    pub fn restore_scroll_position(&self, key: &str) -> Result<(), JsValue> {
+        let Some(saved) = session_storage()
+            .and_then(|storage| storage.get_item(key).ok().flatten())
+            .and_then(|value| value.parse::<f64>().ok())
+        else {
+            return Ok(());
+        };
+
+        let max_scroll =
+            (self.element.scroll_height() as f64 - self.element.client_height() as f64).max(0.0);
+        self.scroll_to_pixels(saved.clamp(0.0, max_scroll))
+    }
 }
 
-#[inline(always)]
-pub fn create_window_storyteller_with_config(
-    config: ScrollStorytellerConfig,
-) -> Result<ScrollStoryteller, JsValue> {
-    ScrollStoryteller::for_window(Some(config))
+/// The ranges whose inclusive `(from, to)` bounds contain `progress`, in the order given. Shared
+/// by `active_ranges` and `SectionTracker` so both agree on the edges
+pub(crate) fn ranges_containing<T>(
+    ranges: &[T],
+    progress: f64,
+    bounds: impl Fn(&T) -> (f64, f64),
+) -> impl Iterator<Item = &T> {
+    ranges.iter().filter(move |range| {
+        let (from, to) = bounds(range);
+        progress >= from && progress <= to
+    })
 }
 
-#[inline(always)]
-pub fn create_element_storyteller(element: HtmlElement) -> Result<ScrollStoryteller, JsValue> {
-    ScrollStoryteller::new(element, None)
+fn active_range_names(ranges: &[NamedRange], progress: &ScrollProgress) -> Vec<String> {
+    ranges_containing(ranges, progress.progress, |range| (range.from, range.to))
+        .map(|range| range.name.clone())
+        .collect()
 }
 
-#[inline(always)]
-pub fn create_element_storyteller_with_config(
-    element: HtmlElement,
-    config: ScrollStorytellerConfig,
-) -> Result<ScrollStoryteller, JsValue> {
-    ScrollStoryteller::new(element, Some(config))
+/// Scroll and resize listeners shared by `ScrollStoryteller` and `ScrollStoryteller2D`. Scroll
+/// events on `scroll_target` reach `dispatch_scroll` through the config's `scroll_mode`, reading
+/// `throttle_ms` on every event so `set_throttle_ms` applies straight away, with a `trailing`
+/// dispatch when asked for. Window resizes reach `on_resize` at most once every
+/// `resize_debounce_ms`, unless `track_resize` is off. Both go when the calling component
+/// unmounts
+fn listen_for_scroll_and_resize<D, R>(
+    scroll_target: &EventTarget,
+    config: &ScrollStorytellerConfig,
+    throttle_ms: Rc<Cell<f64>>,
+    last_scroll_time: Rc<Cell<f64>>,
+    last_resize_time: Rc<Cell<f64>>,
+    dispatch_scroll: D,
+    on_resize: R,
+) -> Result<(), JsValue>
+where
+    D: Fn() + Clone + 'static,
+    R: Fn() + 'static,
+{
+    let window = web_sys::window().ok_or(ScrollError::NoWindow)?;
+    let performance = window
+        .performance()
+        .ok_or_else(|| JsValue::from_str("Performance API unavailable"))?;
+    let resize_performance = performance.clone();
+
+    let scroll_mode = config.scroll_mode;
+    let trailing = config.trailing;
+    let pending_scroll_timeout = Rc::new(Cell::new(None::<TimeoutHandle>));
+    let pending_scroll_timeout_clone = pending_scroll_timeout.clone();
+
+    let scroll_throttle_ms = throttle_ms;
+    let scroll_closure = Closure::wrap(Box::new(move || match scroll_mode {
+        ScrollMode::Throttle => {
+            let now = performance.now();
+            let throttle_ms = scroll_throttle_ms.get();
+
+            // Inline throttling check
+            let last_time = last_scroll_time.get();
+            if now - last_time < throttle_ms {
+                // Catch the final position once the window closes, in case this is the last
+                // event before scrolling stops
+                if trailing && pending_scroll_timeout_clone.get().is_none() {
+                    let pending_scroll_timeout = pending_scroll_timeout_clone.clone();
+                    let last_scroll_time = last_scroll_time.clone();
+                    let performance = performance.clone();
+                    let dispatch_scroll = dispatch_scroll.clone();
+                    pending_scroll_timeout_clone.set(
+                        set_timeout_with_handle(
+                            move || {
+                                pending_scroll_timeout.set(None);
+                                last_scroll_time.set(performance.now());
+                                dispatch_scroll();
+                            },
+                            Duration::from_millis((throttle_ms - (now - last_time)) as u64),
+                        )
+                        .ok(),
+                    );
+                }
+                return;
+            }
+            last_scroll_time.set(now);
+
+            if let Some(handle) = pending_scroll_timeout_clone.take() {
+                handle.clear();
+            }
+            dispatch_scroll();
+        }
+        ScrollMode::Debounce => {
+            // Every event pushes the pending dispatch back until scrolling settles
+            if let Some(handle) = pending_scroll_timeout_clone.take() {
+                handle.clear();
+            }
+
+            pending_scroll_timeout_clone.set(
+                set_timeout_with_handle(
+                    dispatch_scroll.clone(),
+                    Duration::from_millis(scroll_throttle_ms.get() as u64),
+                )
+                .ok(),
+            );
+        }
+    }) as Box<dyn FnMut()>);
+
+    // Passive since callbacks never preventDefault, which keeps scrolling off the main thread
+    let listener_options = AddEventListenerOptions::new();
+    listener_options.set_passive(true);
+    scroll_target.add_event_listener_with_callback_and_add_event_listener_options(
+        "scroll",
+        scroll_closure.as_ref().unchecked_ref(),
+        &listener_options,
+    )?;
+
+    let resize_debounce_ms = config.resize_debounce_ms as f64;
+    let handle = config.track_resize.then(|| {
+        window_event_listener(resize, move |_| {
+            let now = resize_performance.now();
+
+            let last_time = last_resize_time.get();
+            if now - last_time < resize_debounce_ms {
+                return;
+            }
+            last_resize_time.set(now);
+
+            on_resize();
+        })
+    });
+
+    // store the values in the reactive system which stops them from being dropped immediately
+    // attaches their lifetime to the lifetime of the reactive context they're in
+    let _ = StoredValue::new_local(scroll_closure);
+
+    on_cleanup_local(move || {
+        if let Some(handle) = handle {
+            handle.remove();
+        }
+
+        if let Some(timeout) = pending_scroll_timeout.take() {
+            timeout.clear();
+        }
+    });
+
+    Ok(())
+}
+
+/// `measure_progress`, or `measure_viewport_progress` when tracking an element through the
+/// viewport
+fn measure_tracked(
+    element: &Element,
+    tracking: &Tracking,
+    offset_top: OffsetUnit,
+    offset_bottom: OffsetUnit,
+) -> ScrollProgress {
+    match tracking {
+        Tracking::Container => measure_progress(element, offset_top, offset_bottom),
+        Tracking::Viewport(target) => measure_viewport_progress(target, offset_top, offset_bottom),
+    }
+}
+
+/// Reads `target`'s position against the window's viewport, inset by the offsets, into a fresh
+/// `ScrollProgress` (see `viewport_progress`)
+fn measure_viewport_progress(
+    target: &Element,
+    offset_top: OffsetUnit,
+    offset_bottom: OffsetUnit,
+) -> ScrollProgress {
+    let window_height = web_sys::window()
+        .and_then(|window| window.inner_height().ok())
+        .and_then(|height| height.as_f64())
+        .unwrap_or_default();
+    let viewport_top = offset_top.resolve(window_height);
+    let viewport_height = window_height - viewport_top - offset_bottom.resolve(window_height);
+    let rect = target.get_bounding_client_rect();
+
+    viewport_progress(rect.top(), rect.height(), viewport_top, viewport_height)
+}
+
+/// `element_progress` as a `ScrollProgress`, with `scroll_y` the px the element has travelled
+/// since its top met the viewport's bottom edge and `max_scroll()` the whole journey, so the
+/// delta and range helpers read the same as for a container
+fn viewport_progress(
+    top: f64,
+    height: f64,
+    viewport_top: f64,
+    viewport_height: f64,
+) -> ScrollProgress {
+    let travel = viewport_height + height;
+    ScrollProgress {
+        progress: element_progress(top, height, viewport_top, viewport_height),
+        scroll_y: viewport_top + viewport_height - top,
+        scroll_height: travel + viewport_height,
+        viewport_height,
+        overscroll: 0.0,
+    }
+}
+
+/// Reads the container's current size and position into a fresh `ScrollProgress`. Percentage
+/// offsets are resolved against the current size
+fn measure_progress(
+    element: &Element,
+    offset_top: OffsetUnit,
+    offset_bottom: OffsetUnit,
+) -> ScrollProgress {
+    let client_height = element.client_height() as f64;
+    let offset_top = offset_top.resolve(client_height);
+    let scroll_y = element.scroll_top() as f64 + offset_top;
+    let scroll_height = element.scroll_height() as f64;
+    // `clientHeight` for the page too, rather than the visual viewport: it's what `scrollTop`
+    // ranges over, and it holds still while mobile browser chrome shows and hides, so a resize
+    // doesn't move the progress the scroll path computed for the same position
+    let viewport_height = client_height - offset_top - offset_bottom.resolve(client_height);
+
+    ScrollProgress::new(scroll_y, scroll_height, viewport_height)
+}
+
+/// Holds progress at 0.0 when there's less than `min_scrollable_px` to scroll through, returning
+/// whether it should be dispatched to callbacks
+fn hold_unscrollable(progress: &mut ScrollProgress, min_scrollable_px: f64) -> bool {
+    if progress.max_scroll() >= min_scrollable_px {
+        return true;
+    }
+
+    progress.progress = 0.0;
+    false
+}
+
+/// Sets `signal` only when `value` differs, so subscribers aren't woken on every resize
+fn set_if_changed<T: Clone + PartialEq + Send + Sync + 'static>(signal: RwSignal<T>, value: T) {
+    if signal.get_untracked() != value {
+        signal.set(value);
+    }
+}
+
+/// Top and height of `container`'s visible area in viewport coordinates. The page's scrolling
+/// element spans the whole document, so its visible area is the window
+fn visible_area(container: &Element) -> (f64, f64) {
+    let window = web_sys::window();
+    let is_page = window
+        .as_ref()
+        .and_then(|window| window.document())
+        .and_then(|document| document.scrolling_element())
+        .is_some_and(|scrolling_element| scrolling_element == *container);
+
+    if is_page {
+        let height = window
+            .and_then(|window| window.inner_height().ok())
+            .and_then(|height| height.as_f64())
+            .unwrap_or_else(|| container.client_height() as f64);
+        (0.0, height)
+    } else {
+        let top = container.get_bounding_client_rect().top() + container.client_top() as f64;
+        (top, container.client_height() as f64)
+    }
+}
+
+/// Progress of an element `height` px tall at `top` through a visible area, from its top edge
+/// meeting the area's bottom (0.0) to its bottom edge meeting the area's top (1.0)
+#[inline(always)]
+fn element_progress(top: f64, height: f64, viewport_top: f64, viewport_height: f64) -> f64 {
+    let travel = viewport_height + height;
+    if travel <= 0.0 {
+        return 0.0;
+    }
+
+    ((viewport_top + viewport_height - top) / travel).clamp(0.0, 1.0)
+}
+
+/// Reading progress (0.0-1.0) through `element`, e.g. an article, over just the area between a
+/// fixed header and footer: 0.0 until its top reaches the header's bottom edge, 1.0 once its
+/// bottom reaches the footer's top edge. An empty or unmatched selector counts as no header or
+/// footer. Their heights are re-measured on resize, scrolls of any container on the page are
+/// followed, and the listeners are removed when the calling component unmounts
+pub fn reading_progress(
+    element: impl Into<Element>,
+    header_selector: &str,
+    footer_selector: &str,
+) -> Result<ReadSignal<f64>, ScrollError> {
+    let window = web_sys::window().ok_or(ScrollError::NoWindow)?;
+    let document = window.document().ok_or(ScrollError::NoDocument)?;
+    let element = element.into();
+
+    let measure_insets = {
+        let document = document.clone();
+        let header_selector = header_selector.to_owned();
+        let footer_selector = footer_selector.to_owned();
+        move || {
+            (
+                fixed_height(&document, &header_selector),
+                fixed_height(&document, &footer_selector),
+            )
+        }
+    };
+    let insets = Rc::new(Cell::new(measure_insets()));
+
+    let progress = RwSignal::new(0.0);
+    let update = {
+        let insets = insets.clone();
+        let window = window.clone();
+        Rc::new(move || {
+            let (header_height, footer_height) = insets.get();
+            let viewport_height = window
+                .inner_height()
+                .ok()
+                .and_then(|height| height.as_f64())
+                .unwrap_or_default();
+            let rect = element.get_bounding_client_rect();
+
+            set_if_changed(
+                progress,
+                reading_fraction(
+                    rect.top(),
+                    rect.height(),
+                    header_height,
+                    viewport_height - footer_height,
+                ),
+            );
+        })
+    };
+    update();
+
+    // Scroll doesn't bubble, capturing on the document catches inner scroll containers too
+    let scroll_update = update.clone();
+    let on_scroll = Closure::wrap(Box::new(move || scroll_update()) as Box<dyn FnMut()>);
+    let options = AddEventListenerOptions::new();
+    options.set_passive(true);
+    options.set_capture(true);
+    document.add_event_listener_with_callback_and_add_event_listener_options(
+        "scroll",
+        on_scroll.as_ref().unchecked_ref(),
+        &options,
+    )?;
+
+    let handle = window_event_listener(resize, move |_| {
+        insets.set(measure_insets());
+        update();
+    });
+
+    on_cleanup_local(move || {
+        handle.remove();
+
+        let _ = document.remove_event_listener_with_callback_and_bool(
+            "scroll",
+            on_scroll.as_ref().unchecked_ref(),
+            true,
+        );
+    });
+
+    Ok(progress.read_only())
+}
+
+/// Height of the first element matching `selector`, 0.0 when nothing matches
+fn fixed_height(document: &web_sys::Document, selector: &str) -> f64 {
+    document
+        .query_selector(selector)
+        .ok()
+        .flatten()
+        .map(|element| element.get_bounding_client_rect().height())
+        .unwrap_or_default()
+}
+
+/// How much of an element `height` px tall at `top` has scrolled past `readable_top`, out of
+/// the distance it travels before its bottom meets `readable_bottom`. One that fits the readable
+/// area whole is complete once its bottom is in it
+#[inline(always)]
+fn reading_fraction(top: f64, height: f64, readable_top: f64, readable_bottom: f64) -> f64 {
+    let travel = height - (readable_bottom - readable_top);
+    if travel <= 0.0 {
+        return if top + height <= readable_bottom {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    ((readable_top - top) / travel).clamp(0.0, 1.0)
+}
+
+/// Collects inline style properties during a scroll callback and writes them to an element
+/// back to back, with no layout reads in between to force a style recalculation per property
+///
+/// ```ignore
+/// StyleBatch::new()
+///     .set("transform", format!("translateY({}px)", progress.progress * -100.0))
+///     .set("opacity", 1.0 - progress.progress)
+///     .flush(&element);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct StyleBatch {
+    declarations: Vec<(String, String)>,
+}
+
+impl StyleBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `property` to `value`, replacing any value set earlier in the batch
+    pub fn set(&mut self, property: &str, value: impl fmt::Display) -> &mut Self {
+        let value = value.to_string();
+        match self
+            .declarations
+            .iter_mut()
+            .find(|(name, _)| name == property)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.declarations.push((property.to_owned(), value)),
+        }
+        self
+    }
+
+    /// Sets each property in the batch on `element`'s inline style, leaving properties outside
+    /// the batch alone. A no-op for elements without an inline style to set, as with `set_cursor`
+    pub fn flush(&self, element: &web_sys::Element) {
+        let Some(element) = element.dyn_ref::<HtmlElement>() else {
+            return;
+        };
+
+        let style = element.style();
+        for (property, value) in &self.declarations {
+            let _ = style.set_property(property, value);
+        }
+    }
+}
+
+/// `value` with `separator` between each group of three digits, e.g. `1,234,567` for counters
+pub fn format_thousands(value: i64, separator: char) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if value < 0 {
+        formatted.push('-');
+    }
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(separator);
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Frame of an image sequence `frame_count` frames long to show at `progress`, each frame getting
+/// an equal share of the scroll. Always within `0..frame_count`, and 0 when there are no frames
+#[inline(always)]
+pub fn frame_index_at(progress: f64, frame_count: usize) -> usize {
+    let last_frame = frame_count.saturating_sub(1);
+    // NaN casts to 0
+    ((progress.clamp(0.0, 1.0) * frame_count as f64) as usize).min(last_frame)
+}
+
+/// Whether the browser can drive CSS animations from scroll position (`animation-timeline`)
+pub fn supports_scroll_timeline() -> bool {
+    web_sys::css::supports_with_value("animation-timeline", "scroll()").unwrap_or(false)
+}
+
+/// Source of `ScrollStoryteller::id`
+static STORYTELLER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Keyframes names for `inject_scroll_animation`, unique across storytellers
+static SCROLL_ANIMATION_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Adds a `@keyframes` rule animating `property` and runs it on `el` against its nearest
+/// scroller's timeline. The rule and animation are removed when the calling component unmounts
+fn inject_scroll_animation(
+    el: &HtmlElement,
+    property: &str,
+    from_value: &str,
+    to_value: &str,
+) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or(ScrollError::NoDocument)?;
+    let head = document
+        .head()
+        .ok_or_else(|| JsValue::from_str("No document head"))?;
+
+    let name = format!(
+        "generik-scroll-{}",
+        SCROLL_ANIMATION_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let rule = document.create_element("style")?;
+    rule.set_text_content(Some(&format!(
+        "@keyframes {} {{ from {{ {}: {}; }} to {{ {}: {}; }} }}",
+        name, property, from_value, property, to_value
+    )));
+    head.append_child(&rule)?;
+
+    // The shorthand resets animation-timeline, so it has to go first
+    let style = el.style();
+    style.set_property("animation", &format!("{} linear both", name))?;
+    style.set_property("animation-timeline", "scroll(nearest block)")?;
+
+    let el = el.clone();
+    on_cleanup_local(move || {
+        rule.remove();
+        let style = el.style();
+        let _ = style.remove_property("animation");
+        let _ = style.remove_property("animation-timeline");
+    });
+
+    Ok(())
+}
+
+/// Scroll position that puts `element` at `progress`, allowing for the top and bottom offsets
+fn progress_scroll_top(
+    element: &Element,
+    tracking: &Tracking,
+    current: ScrollProgress,
+    (offset_top, offset_bottom): (OffsetUnit, OffsetUnit),
+    progress: f64,
+) -> f64 {
+    match tracking {
+        Tracking::Container => progress_to_scroll_top(
+            progress,
+            offset_top.resolve(element.client_height() as f64),
+            current.max_scroll(),
+        ),
+        // Its travel moves 1:1 with the page, so scroll by however much is left to go
+        Tracking::Viewport(target) => {
+            let now = measure_viewport_progress(target, offset_top, offset_bottom);
+            element.scroll_top() as f64 + progress_to_scroll_top(progress, 0.0, now.max_scroll())
+                - now.scroll_y
+        }
+    }
+}
+
+/// `scrollTop` for `progress` given the resolved `offset_top` in px
+fn progress_to_scroll_top(progress: f64, offset_top: f64, max_scroll: f64) -> f64 {
+    progress.clamp(0.0, 1.0) * max_scroll - offset_top
+}
+
+/// Inverse of `progress_to_scroll_top`, clamped to 0.0-1.0. A container with nothing to scroll
+/// is complete, as in `ScrollProgress::calculate_progress`
+fn scroll_top_to_progress(scroll_top: f64, offset_top: f64, max_scroll: f64) -> f64 {
+    if max_scroll <= 0.0 {
+        return 1.0;
+    }
+
+    ((scroll_top + offset_top) / max_scroll).clamp(0.0, 1.0)
+}
+
+/// Scrolls `element` to `top`, smoothly if asked unless the user prefers reduced motion
+fn scroll_element_to(element: &Element, top: f64, smooth: bool) {
+    let options = ScrollToOptions::new();
+    options.set_top(top);
+    if smooth && !prefers_reduced_motion() {
+        options.set_behavior(ScrollBehavior::Smooth);
+    }
+
+    element.scroll_with_scroll_to_options(&options);
+}
+
+/// Sets or clears the inline `cursor`, a no-op for elements without an inline style to set, e.g.
+/// SVG, as web-sys only exposes `style` on `HtmlElement`
+fn set_cursor(element: &Element, cursor: Option<&str>) -> Result<(), JsValue> {
+    let Some(element) = element.dyn_ref::<HtmlElement>() else {
+        return Ok(());
+    };
+
+    match cursor {
+        Some(cursor) => element.style().set_property("cursor", cursor),
+        None => element.style().remove_property("cursor").map(|_| ()),
+    }
+}
+
+/// Form fields and editable content, whose keys belong to the text they hold
+const EDITABLE_SELECTOR: &str =
+    "input, textarea, select, [contenteditable]:not([contenteditable='false'])";
+
+/// Whether `target` is, or sits inside, an element edited through the keyboard
+fn is_editable_target(target: Option<&EventTarget>) -> bool {
+    closest_match(target, EDITABLE_SELECTOR)
+}
+
+/// Whether `target` is, or sits inside, an element matching `selector`
+fn closest_match(target: Option<&EventTarget>, selector: &str) -> bool {
+    target
+        .and_then(|target| target.dyn_ref::<Element>())
+        .and_then(|element| element.closest(selector).ok().flatten())
+        .is_some()
+}
+
+fn prefers_reduced_motion() -> bool {
+    matches_media("(prefers-reduced-motion: reduce)")
+}
+
+/// Pixels per line for wheel events reported in lines, close to what browsers scroll by
+const WHEEL_LINE_HEIGHT_PX: f64 = 16.0;
+
+/// Converts a wheel `delta` in `delta_mode` units (0 pixels, 1 lines, 2 pages) to pixels, with
+/// a page being `page_height`
+fn normalize_wheel_delta(delta: f64, delta_mode: u32, page_height: f64) -> f64 {
+    match delta_mode {
+        WheelEvent::DOM_DELTA_LINE => delta * WHEEL_LINE_HEIGHT_PX,
+        WheelEvent::DOM_DELTA_PAGE => delta * page_height,
+        _ => delta,
+    }
+}
+
+/// Distance the arrow keys scroll by, roughly three lines of text
+const KEYBOARD_STEP_PX: f64 = 40.0;
+
+/// What a key press does in `enable_keyboard`
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum KeyboardScroll {
+    By(f64),
+    ToProgress(f64),
+}
+
+impl KeyboardScroll {
+    fn from_key(key: &str, viewport_height: f64) -> Option<Self> {
+        match key {
+            "PageDown" => Some(KeyboardScroll::By(viewport_height)),
+            "PageUp" => Some(KeyboardScroll::By(-viewport_height)),
+            "ArrowDown" => Some(KeyboardScroll::By(KEYBOARD_STEP_PX)),
+            "ArrowUp" => Some(KeyboardScroll::By(-KEYBOARD_STEP_PX)),
+            "Home" => Some(KeyboardScroll::ToProgress(0.0)),
+            "End" => Some(KeyboardScroll::ToProgress(1.0)),
+            _ => None,
+        }
+    }
+}
+
+const DRAG_SCROLL_EVENTS: [&str; 5] = [
+    "pointerdown",
+    "pointermove",
+    "pointerup",
+    "pointercancel",
+    "pointerleave",
+];
+
+/// Presses `enable_drag_scroll` leaves alone, as they focus, type into or activate what's under
+/// the pointer. Links aren't included so a gallery of them still drags
+const DRAG_IGNORE_SELECTOR: &str = "input, textarea, select, button, label, \
+     [contenteditable]:not([contenteditable='false'])";
+
+/// Total pointer travel in px below which a drag still counts as a click
+const DRAG_CLICK_THRESHOLD_PX: f64 = 5.0;
+
+/// An in-progress `enable_drag_scroll` drag
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct DragScroll {
+    pointer_id: i32,
+    last_x: f64,
+    last_y: f64,
+    distance: f64,
+}
+
+impl DragScroll {
+    fn new(pointer_id: i32, x: f64, y: f64) -> Self {
+        Self {
+            pointer_id,
+            last_x: x,
+            last_y: y,
+            distance: 0.0,
+        }
+    }
+
+    /// Records the pointer at `(x, y)` and returns how far it moved since the last position
+    fn move_to(&mut self, x: f64, y: f64) -> (f64, f64) {
+        let (dx, dy) = (x - self.last_x, y - self.last_y);
+        self.last_x = x;
+        self.last_y = y;
+        self.distance += dx.hypot(dy);
+        (dx, dy)
+    }
+
+    fn is_click(&self) -> bool {
+        self.distance < DRAG_CLICK_THRESHOLD_PX
+    }
+}
+
+/// A running `animated_scroll_to_progress`, moved from frame to frame until it finishes or its
+/// pending frame is canceled
+struct ScrollAnimation {
+    element: Element,
+    performance: Performance,
+    pending_frame: Rc<Cell<Option<AnimationFrameRequestHandle>>>,
+    start_time: f64,
+    from: f64,
+    to: f64,
+    duration_ms: f64,
+    easing: EasingFunction,
+}
+
+impl ScrollAnimation {
+    fn next_frame(self) {
+        let pending_frame = self.pending_frame.clone();
+        pending_frame.set(request_animation_frame_with_handle(move || self.step()).ok());
+    }
+
+    fn step(self) {
+        self.pending_frame.set(None);
+
+        let t = if self.duration_ms > 0.0 {
+            ((self.performance.now() - self.start_time) / self.duration_ms).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        // Instant so a `scroll-behavior: smooth` stylesheet doesn't fight the animation
+        let options = ScrollToOptions::new();
+        options.set_top(self.from + (self.to - self.from) * self.easing.apply(t));
+        options.set_behavior(ScrollBehavior::Instant);
+        self.element.scroll_with_scroll_to_options(&options);
+
+        if t < 1.0 {
+            self.next_frame();
+        }
+    }
+}
+
+/// Drives `smoothing`: eases the progress handed to callbacks toward the last measured one, one
+/// animation frame at a time, until it's within `SMOOTHING_EPSILON_PX`
+#[derive(Clone)]
+struct ProgressSmoother<P: Smoothed> {
+    /// Hands each eased progress to the storyteller's callbacks
+    dispatch: Rc<dyn Fn(P)>,
+    factor: f64,
+    /// What callbacks were last handed, `None` until the first scroll
+    displayed: Rc<Cell<Option<P>>>,
+    /// Where easing is headed, `None` until the first scroll
+    target: Rc<Cell<Option<P>>>,
+    pending_frame: Rc<Cell<Option<AnimationFrameRequestHandle>>>,
+}
+
+impl<P: Smoothed> ProgressSmoother<P> {
+    fn new(dispatch: Rc<dyn Fn(P)>, factor: f64) -> Self {
+        Self {
+            dispatch,
+            factor,
+            displayed: Rc::new(Cell::new(None)),
+            target: Rc::new(Cell::new(None)),
+            pending_frame: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Eases toward `target` from wherever callbacks were last left, `previous` for the first
+    /// scroll. A loop that's already running just picks up the new target
+    fn retarget(&self, previous: P, target: P) {
+        if self.displayed.get().is_none() {
+            self.displayed.set(Some(previous));
+        }
+        self.target.set(Some(target));
+
+        if self.pending_frame.get().is_none() {
+            self.clone().next_frame();
+        }
+    }
+
+    /// Stops easing and takes `progress` as displayed, for resizes and refreshes that dispatch
+    /// the measured progress straight away
+    fn snap(&self, progress: P) {
+        self.cancel();
+        self.displayed.set(Some(progress));
+        self.target.set(Some(progress));
+    }
+
+    fn cancel(&self) {
+        if let Some(frame) = self.pending_frame.take() {
+            frame.cancel();
+        }
+    }
+
+    fn next_frame(self) {
+        let pending_frame = self.pending_frame.clone();
+        pending_frame.set(request_animation_frame_with_handle(move || self.step()).ok());
+    }
+
+    fn step(self) {
+        self.pending_frame.set(None);
+
+        let Some(target) = self.target.get() else {
+            return;
+        };
+        let displayed = self.displayed.get().unwrap_or(target);
+        let (next, settled) = displayed.smooth_step(target, self.factor);
+        self.displayed.set(Some(next));
+
+        (self.dispatch)(next);
+
+        if !settled {
+            self.next_frame();
+        }
+    }
+}
+
+/// One `smoothing` frame: `displayed` moved `factor` of the way to `target`, and whether it
+/// arrived. Only `scroll_y` is interpolated, the rest is derived from the target's measurements
+fn smooth_step(
+    displayed: ScrollProgress,
+    target: ScrollProgress,
+    factor: f64,
+) -> (ScrollProgress, bool) {
+    let remaining = target.scroll_y - displayed.scroll_y;
+    if remaining.abs() <= SMOOTHING_EPSILON_PX {
+        return (target, true);
+    }
+
+    let mut next = target;
+    next.scroll_y = displayed.scroll_y + remaining * factor;
+    next.calculate_progress();
+    (next, false)
+}
+
+/// Progress `ProgressSmoother` can ease toward a target, one frame at a time
+trait Smoothed: Copy + 'static {
+    /// One frame: `self` moved `factor` of the way to `target`, and whether it arrived
+    fn smooth_step(self, target: Self, factor: f64) -> (Self, bool);
+}
+
+impl Smoothed for ScrollProgress {
+    fn smooth_step(self, target: Self, factor: f64) -> (Self, bool) {
+        smooth_step(self, target, factor)
+    }
+}
+
+impl Smoothed for Scroll2DProgress {
+    /// Each axis eased on its own, arriving once both have
+    fn smooth_step(self, target: Self, factor: f64) -> (Self, bool) {
+        let (horizontal, horizontal_settled) =
+            smooth_step(self.horizontal(), target.horizontal(), factor);
+        let (vertical, vertical_settled) = smooth_step(self.vertical(), target.vertical(), factor);
+
+        let next = Scroll2DProgress::new(
+            horizontal.scroll_y,
+            target.scroll_width,
+            target.viewport_width,
+            vertical.scroll_y,
+            target.scroll_height,
+            target.viewport_height,
+        );
+        (next, horizontal_settled && vertical_settled)
+    }
+}
+
+/// Watches an element and its children for size changes, which fire no scroll or resize event
+/// when content grows or shrinks on its own (e.g. images loading, items appended). Children
+/// added later are watched as they arrive
+struct ContentSizeObserver {
+    resize_observer: ResizeObserver,
+    mutation_observer: MutationObserver,
+    _on_resize: Closure<dyn FnMut()>,
+    _on_mutation: Closure<dyn FnMut()>,
+}
+
+impl ContentSizeObserver {
+    fn new<F>(element: &Element, on_change: F) -> Result<Self, JsValue>
+    where
+        F: Fn() + 'static,
+    {
+        let on_change = Rc::new(on_change);
+
+        let resize_change = on_change.clone();
+        let on_resize = Closure::wrap(Box::new(move || resize_change()) as Box<dyn FnMut()>);
+        let resize_observer = ResizeObserver::new(on_resize.as_ref().unchecked_ref())?;
+        observe_with_children(&resize_observer, element);
+
+        // A removed child resizes nothing that's still observed, so report every change to the
+        // children as well. Observing an already observed element is a no-op
+        let observed = resize_observer.clone();
+        let container = element.clone();
+        let on_mutation = Closure::wrap(Box::new(move || {
+            observe_with_children(&observed, &container);
+            on_change();
+        }) as Box<dyn FnMut()>);
+        let mutation_observer = MutationObserver::new(on_mutation.as_ref().unchecked_ref())?;
+        let options = MutationObserverInit::new();
+        options.set_child_list(true);
+        mutation_observer.observe_with_options(element, &options)?;
+
+        Ok(Self {
+            resize_observer,
+            mutation_observer,
+            _on_resize: on_resize,
+            _on_mutation: on_mutation,
+        })
+    }
+
+    fn disconnect(&self) {
+        self.resize_observer.disconnect();
+        self.mutation_observer.disconnect();
+    }
+}
+
+fn observe_with_children(observer: &ResizeObserver, element: &Element) {
+    observer.observe(element);
+
+    let mut child = element.first_element_child();
+    while let Some(element) = child {
+        observer.observe(&element);
+        child = element.next_element_sibling();
+    }
+}
+
+const PERSIST_SCROLL_DEBOUNCE_MS: u32 = 150;
+
+/// User input that stops an animated scroll so it doesn't fight the user for the position
+const ANIMATION_INTERRUPT_EVENTS: [&str; 4] = ["wheel", "touchstart", "pointerdown", "keydown"];
+
+type CompositeCallbacks = Rc<std::cell::RefCell<Vec<Box<dyn Fn(ScrollProgress, usize)>>>>;
+
+/// Tracks several scroll containers as one continuous story, weighting each section by its
+/// scrollable distance so progress runs 0.0-1.0 across all of them
+pub struct CompositeStoryteller {
+    sections: Vec<ScrollStoryteller>,
+    last_progress: RwSignal<ScrollProgress>,
+    active_index: RwSignal<usize>,
+    callbacks: CompositeCallbacks,
+}
+
+impl CompositeStoryteller {
+    pub fn new(
+        elements: Vec<HtmlElement>,
+        config: Option<ScrollStorytellerConfig>,
+    ) -> Result<Self, JsValue> {
+        let config = config.unwrap_or_default();
+        let sections = elements
+            .into_iter()
+            .map(|element| ScrollStoryteller::new(element, Some(config.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let section_progress: Rc<Vec<ReadSignal<ScrollProgress>>> =
+            Rc::new(sections.iter().map(|section| section.progress()).collect());
+        let combine = move || {
+            let (scroll_y, scroll_height, viewport_height) = section_progress.iter().fold(
+                (0.0, 0.0, 0.0),
+                |(scroll_y, scroll_height, viewport_height), progress| {
+                    let progress = progress.get_untracked();
+                    (
+                        scroll_y + progress.scroll_y.clamp(0.0, progress.max_scroll()),
+                        scroll_height + progress.scroll_height,
+                        viewport_height + progress.viewport_height,
+                    )
+                },
+            );
+            ScrollProgress::new(scroll_y, scroll_height, viewport_height)
+        };
+
+        let last_progress = RwSignal::new(combine());
+        let active_index = RwSignal::new(0);
+        let callbacks: CompositeCallbacks = Rc::new(std::cell::RefCell::new(Vec::with_capacity(8)));
+
+        for (index, section) in sections.iter().enumerate() {
+            let combine = combine.clone();
+            let callbacks = callbacks.clone();
+            section.on_scroll(move |_| {
+                let progress = combine();
+                last_progress.set(progress);
+                active_index.set(index);
+
+                for callback in callbacks.borrow().iter() {
+                    callback(progress, index);
+                }
+            });
+        }
+
+        Ok(Self {
+            sections,
+            last_progress,
+            active_index,
+            callbacks,
+        })
+    }
+
+    #[inline(always)]
+    pub fn on_scroll<F>(&self, callback: F)
+    where
+        F: Fn(ScrollProgress, usize) + 'static,
+    {
+        self.callbacks.borrow_mut().push(Box::new(callback));
+    }
+
+    #[inline(always)]
+    pub fn progress(&self) -> ReadSignal<ScrollProgress> {
+        self.last_progress.read_only()
+    }
+
+    /// Index of the section that most recently scrolled
+    #[inline(always)]
+    pub fn active_index(&self) -> ReadSignal<usize> {
+        self.active_index.read_only()
+    }
+
+    #[inline(always)]
+    pub fn sections(&self) -> &[ScrollStoryteller] {
+        &self.sections
+    }
+}
+
+/// Scroll progress along both axes of a container that scrolls horizontally and vertically, such
+/// as a map or data grid. Each axis is calculated the same way as `ScrollProgress`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Scroll2DProgress {
+    pub x: f64,
+    pub y: f64,
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+    pub scroll_width: f64,
+    pub scroll_height: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+}
+
+impl Scroll2DProgress {
+    #[inline(always)]
+    pub fn new(
+        scroll_x: f64,
+        scroll_width: f64,
+        viewport_width: f64,
+        scroll_y: f64,
+        scroll_height: f64,
+        viewport_height: f64,
+    ) -> Self {
+        Self {
+            x: ScrollProgress::new(scroll_x, scroll_width, viewport_width).progress,
+            y: ScrollProgress::new(scroll_y, scroll_height, viewport_height).progress,
+            scroll_x,
+            scroll_y,
+            scroll_width,
+            scroll_height,
+            viewport_width,
+            viewport_height,
+        }
+    }
+
+    /// The horizontal axis as a `ScrollProgress`, with `scroll_y`/`scroll_height`/
+    /// `viewport_height` holding the horizontal measurements, for its easing and range helpers
+    #[inline(always)]
+    pub fn horizontal(&self) -> ScrollProgress {
+        ScrollProgress::new(self.scroll_x, self.scroll_width, self.viewport_width)
+    }
+
+    /// The vertical axis as a `ScrollProgress`
+    #[inline(always)]
+    pub fn vertical(&self) -> ScrollProgress {
+        ScrollProgress::new(self.scroll_y, self.scroll_height, self.viewport_height)
+    }
+}
+
+type Scroll2DCallbacks = Rc<std::cell::RefCell<Vec<Box<dyn Fn(Scroll2DProgress)>>>>;
+
+/// Tracks both scroll axes of one element from a single scroll listener. `offset_top` and
+/// `offset_bottom` apply to the vertical axis only. Scroll gating, resizes and `smoothing`
+/// follow the config the same way as for `ScrollStoryteller`
+pub struct ScrollStoryteller2D {
+    last_progress: RwSignal<Scroll2DProgress>,
+    callbacks: Scroll2DCallbacks,
+    /// The scroll listener's `throttle_ms`, read on every event
+    throttle_ms: Rc<Cell<f64>>,
+}
+
+impl ScrollStoryteller2D {
+    pub fn new(
+        element: HtmlElement,
+        config: Option<ScrollStorytellerConfig>,
+    ) -> Result<Self, JsValue> {
+        let config = config.unwrap_or_default();
+        let offset_top = config.offset_top;
+        let offset_bottom = config.offset_bottom;
+
+        let measure = move |element: &HtmlElement| {
+            let client_height = element.client_height() as f64;
+            let offset_top = offset_top.resolve(client_height);
+            Scroll2DProgress::new(
+                element.scroll_left() as f64,
+                element.scroll_width() as f64,
+                element.client_width() as f64,
+                element.scroll_top() as f64 + offset_top,
+                element.scroll_height() as f64,
+                client_height - offset_top - offset_bottom.resolve(client_height),
+            )
+        };
+
+        let initial_progress = measure(&element);
+        let last_progress = RwSignal::new(initial_progress);
+        let callbacks: Scroll2DCallbacks = Rc::new(std::cell::RefCell::new(Vec::with_capacity(8)));
+        let throttle_ms = Rc::new(Cell::new(config.throttle_ms as f64));
+        let smoother = config
+            .smoothing
+            .filter(|factor| *factor > 0.0 && *factor < 1.0)
+            .map(|factor| {
+                let callbacks = callbacks.clone();
+                let dispatch = move |progress: Scroll2DProgress| {
+                    for callback in callbacks.borrow().iter() {
+                        callback(progress);
+                    }
+                };
+                ProgressSmoother::new(Rc::new(dispatch), factor)
+            });
+
+        let dispatch_element = element.clone();
+        let dispatch_callbacks = callbacks.clone();
+        let scroll_smoother = smoother.clone();
+        let dispatch_scroll = move || {
+            let previous_progress = last_progress.get_untracked();
+            let progress = measure(&dispatch_element);
+
+            // e.g. a trailing dispatch landing on the position the leading one already reported
+            if progress == previous_progress {
+                return;
+            }
+            last_progress.set(progress);
+
+            if let Some(smoother) = &scroll_smoother {
+                smoother.retarget(previous_progress, progress);
+                return;
+            }
+
+            for callback in dispatch_callbacks.borrow().iter() {
+                callback(progress);
+            }
+        };
+
+        // Both axes' viewport sizes change on resize, so just re-measure
+        let resize_element = element.clone();
+        let resize_callbacks = callbacks.clone();
+        let resize_smoother = smoother.clone();
+        let on_resize = move || {
+            let progress = measure(&resize_element);
+            last_progress.set(progress);
+
+            if let Some(smoother) = &resize_smoother {
+                smoother.snap(progress);
+            }
+            for callback in resize_callbacks.borrow().iter() {
+                callback(progress);
+            }
+        };
+
+        listen_for_scroll_and_resize(
+            &element,
+            &config,
+            throttle_ms.clone(),
+            Rc::new(Cell::new(0.0)),
+            Rc::new(Cell::new(0.0)),
+            dispatch_scroll,
+            on_resize,
+        )?;
+
+        on_cleanup_local(move || {
+            if let Some(smoother) = &smoother {
+                smoother.cancel();
+            }
+        });
+
+        if config.run_straight_away {
+            let callbacks_clone = callbacks.clone();
+            request_animation_frame(move || {
+                for callback in callbacks_clone.borrow().iter() {
+                    callback(initial_progress);
+                }
+            });
+        }
+
+        Ok(Self {
+            last_progress,
+            callbacks,
+            throttle_ms,
+        })
+    }
+
+    /// Changes the throttle (or debounce) window from the next scroll event on, as with
+    /// `ScrollStoryteller::set_throttle_ms`
+    pub fn set_throttle_ms(&self, ms: u32) {
+        self.throttle_ms.set(ms as f64);
+    }
+
+    #[inline(always)]
+    pub fn on_scroll<F>(&self, callback: F)
+    where
+        F: Fn(Scroll2DProgress) + 'static,
+    {
+        self.callbacks.borrow_mut().push(Box::new(callback));
+    }
+
+    #[inline(always)]
+    pub fn progress(&self) -> ReadSignal<Scroll2DProgress> {
+        self.last_progress.read_only()
+    }
+}
+
+#[inline(always)]
+pub fn create_window_storyteller() -> Result<ScrollStoryteller, ScrollError> {
+    ScrollStoryteller::for_window(None)
+}
+
+#[inline(always)]
+pub fn create_window_storyteller_with_config(
+    config: ScrollStorytellerConfig,
+) -> Result<ScrollStoryteller, ScrollError> {
+    ScrollStoryteller::for_window(Some(config))
+}
+
+#[inline(always)]
+pub fn create_element_storyteller(
+    element: impl Into<Element>,
+) -> Result<ScrollStoryteller, JsValue> {
+    ScrollStoryteller::new(element, None)
+}
+
+#[inline(always)]
+pub fn create_element_storyteller_with_config(
+    element: impl Into<Element>,
+    config: ScrollStorytellerConfig,
+) -> Result<ScrollStoryteller, JsValue> {
+    ScrollStoryteller::new(element, Some(config))
+}
+
+/// A storyteller whose progress follows `element` through the window's viewport, see
+/// `ScrollStoryteller::for_element_in_viewport`
+#[inline(always)]
+pub fn create_element_viewport_storyteller(
+    element: impl Into<Element>,
+) -> Result<ScrollStoryteller, ScrollError> {
+    ScrollStoryteller::for_element_in_viewport(element, None)
+}
+
+#[inline(always)]
+pub fn create_element_storyteller_2d(element: HtmlElement) -> Result<ScrollStoryteller2D, JsValue> {
+    ScrollStoryteller2D::new(element, None)
+}
+
+#[inline(always)]
+pub fn create_element_storyteller_2d_with_config(
+    element: HtmlElement,
+    config: ScrollStorytellerConfig,
+) -> Result<ScrollStoryteller2D, JsValue> {
+    ScrollStoryteller2D::new(element, Some(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        active_range_names, element_progress, format_thousands, frame_index_at, hold_unscrollable,
+        normalize_wheel_delta, progress_to_scroll_top, reading_fraction, sample_easing,
+        scroll_top_to_progress, smooth_step, supports_scroll_timeline, viewport_progress,
+        CrossingTracker, DragScroll, EasingFunction, KeyboardScroll, NamedRange, OffsetUnit,
+        RangeEvent, RangeTracker, Scroll2DProgress, ScrollDirection, ScrollMode, ScrollProgress,
+        ScrollStorytellerConfig, Smoothed, StyleBatch, TaggedCallbacks,
+    };
+    use serde_lite::{Deserialize, Intermediate, Serialize};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    fn at(progress: f64) -> ScrollProgress {
+        let mut scroll_progress = ScrollProgress::new(0.0, 0.0, 0.0);
+        scroll_progress.progress = progress;
+        scroll_progress
+    }
+
+    #[test]
+    fn extended_easing_reference_values() {
+        // (easing, [f(0.25), f(0.5), f(0.75)]) checked against easings.net formulas
+        let table = [
+            (
+                EasingFunction::EaseInQuart,
+                [0.00390625, 0.0625, 0.31640625],
+            ),
+            (
+                EasingFunction::EaseOutQuart,
+                [0.68359375, 0.9375, 0.99609375],
+            ),
+            (EasingFunction::EaseInOutQuart, [0.03125, 0.5, 0.96875]),
+            (
+                EasingFunction::EaseInQuint,
+                [0.0009765625, 0.03125, 0.2373046875],
+            ),
+            (
+                EasingFunction::EaseOutQuint,
+                [0.7626953125, 0.96875, 0.9990234375],
+            ),
+            (EasingFunction::EaseInOutQuint, [0.015625, 0.5, 0.984375]),
+            (
+                EasingFunction::EaseInExpo,
+                [0.0055242717, 0.03125, 0.1767766953],
+            ),
+            (
+                EasingFunction::EaseOutExpo,
+                [0.8232233047, 0.96875, 0.9944757283],
+            ),
+            (EasingFunction::EaseInOutExpo, [0.015625, 0.5, 0.984375]),
+            (
+                EasingFunction::EaseInCirc,
+                [0.0317541634, 0.1339745962, 0.3385621722],
+            ),
+            (
+                EasingFunction::EaseOutCirc,
+                [0.6614378278, 0.8660254038, 0.9682458366],
+            ),
+            (
+                EasingFunction::EaseInOutCirc,
+                [0.0669872981, 0.5, 0.9330127019],
+            ),
+        ];
+
+        for (easing, expected) in table {
+            for (t, want) in [0.25, 0.5, 0.75].into_iter().zip(expected) {
+                let got = at(t).eased(easing);
+                assert!(
+                    (got - want).abs() < 1e-9,
+                    "{:?} at {}: got {}, want {}",
+                    easing,
+                    t,
+                    got,
+                    want
+                );
+            }
+
+            assert_eq!(at(0.0).eased(easing), 0.0, "{:?} at 0.0", easing);
+            assert_eq!(at(1.0).eased(easing), 1.0, "{:?} at 1.0", easing);
+        }
+    }
+
+    #[test]
+    fn config_round_trips_through_json() {
+        let config = ScrollStorytellerConfig {
+            throttle_ms: 32,
+            scroll_mode: ScrollMode::Debounce,
+            trailing: true,
+            smooth_scroll: false,
+            offset_top: OffsetUnit::Pixels(64.5),
+            offset_bottom: OffsetUnit::Percent(10.0),
+            run_straight_away: true,
+            resize_debounce_ms: 100,
+            css_scroll_timeline: false,
+            min_scrollable_px: 120.0,
+            track_resize: false,
+            smoothing: Some(0.15),
+        };
+
+        let json = serde_json::to_string(&config.serialize().unwrap()).unwrap();
+        let intermediate: Intermediate = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            ScrollStorytellerConfig::deserialize(&intermediate).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn style_batch_keeps_the_last_value_per_property() {
+        let mut batch = StyleBatch::new();
+        batch
+            .set("transform", "translateY(10px)")
+            .set("opacity", 0.5)
+            .set("transform", "none");
+
+        // In the order properties were first set
+        assert_eq!(
+            batch.declarations,
+            [
+                ("transform".to_string(), "none".to_string()),
+                ("opacity".to_string(), "0.5".to_string())
+            ]
+        );
+        assert!(StyleBatch::new().declarations.is_empty());
+    }
+
+    #[test]
+    fn remove_tagged_inside_a_dispatch_applies_once_it_finishes() {
+        let callbacks = Rc::new(TaggedCallbacks::with_capacity(2));
+        let calls = Rc::new(Cell::new(0));
+
+        let one_shot = callbacks.clone();
+        let one_shot_calls = calls.clone();
+        callbacks.push(
+            Some(Rc::from("once")),
+            Box::new(move |_| {
+                one_shot_calls.set(one_shot_calls.get() + 1);
+                one_shot.remove_tagged("once");
+            }),
+        );
+        let untagged_calls = calls.clone();
+        callbacks.push(
+            None,
+            Box::new(move |_| untagged_calls.set(untagged_calls.get() + 10)),
+        );
+
+        callbacks.dispatch(at(0.5));
+        assert_eq!(calls.get(), 11);
+
+        callbacks.dispatch(at(0.6));
+        assert_eq!(calls.get(), 21);
+    }
+
+    #[test]
+    fn smoothing_eases_toward_the_target_then_snaps() {
+        let target = ScrollProgress::new(800.0, 1000.0, 200.0);
+        let mut displayed = ScrollProgress::new(0.0, 1000.0, 200.0);
+
+        let (next, settled) = smooth_step(displayed, target, 0.5);
+        assert!(!settled);
+        assert_eq!(next.scroll_y, 400.0);
+        assert_eq!(next.progress, 0.5);
+
+        let mut frames = 0;
+        loop {
+            let (next, settled) = smooth_step(displayed, target, 0.5);
+            displayed = next;
+            frames += 1;
+            if settled {
+                break;
+            }
+        }
+        assert_eq!(displayed, target);
+        // 11 halvings take 800px to within 0.5px, then one frame snaps to the target
+        assert_eq!(frames, 12);
+    }
+
+    #[test]
+    fn smoothing_2d_eases_both_axes_until_both_arrive() {
+        let target = Scroll2DProgress::new(800.0, 1000.0, 200.0, 400.0, 600.0, 200.0);
+        let mut displayed = Scroll2DProgress::new(0.0, 1000.0, 200.0, 0.0, 600.0, 200.0);
+
+        let (next, settled) = displayed.smooth_step(target, 0.5);
+        assert!(!settled);
+        assert_eq!((next.scroll_x, next.scroll_y), (400.0, 200.0));
+        assert_eq!((next.x, next.y), (0.5, 0.5));
+
+        let mut frames = 0;
+        loop {
+            let (next, settled) = displayed.smooth_step(target, 0.5);
+            displayed = next;
+            frames += 1;
+            if settled {
+                break;
+            }
+        }
+        assert_eq!(displayed, target);
+        // The longer horizontal distance decides, as for the 1D case
+        assert_eq!(frames, 12);
+    }
+
+    #[test]
+    fn easing_curve_samples_evenly_from_zero_to_one() {
+        assert_eq!(
+            EasingFunction::EaseIn.sample_curve(4),
+            vec![
+                (0.0, 0.0),
+                (0.25, 0.0625),
+                (0.5, 0.25),
+                (0.75, 0.5625),
+                (1.0, 1.0)
+            ]
+        );
+        assert_eq!(
+            EasingFunction::Linear.sample_curve(0),
+            vec![(0.0, 0.0), (1.0, 1.0)]
+        );
+        assert_eq!(
+            sample_easing(EasingFunction::EaseOutCubic, 10),
+            EasingFunction::EaseOutCubic.sample_curve(10)
+        );
+    }
+
+    #[test]
+    fn easing_round_trips_through_json() {
+        for easing in [EasingFunction::Linear, EasingFunction::EaseInOutExpo] {
+            let json = serde_json::to_string(&easing.serialize().unwrap()).unwrap();
+            let intermediate: Intermediate = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(EasingFunction::deserialize(&intermediate).unwrap(), easing);
+        }
+    }
+
+    #[test]
+    fn overscroll_is_reported_while_progress_stays_clamped() {
+        let above = ScrollProgress::new(-40.0, 1000.0, 200.0);
+        assert_eq!(above.overscroll, -40.0);
+        // Held at the lower clamp of viewport_height / scroll_height
+        assert_eq!(above.progress, 0.2);
+
+        let below = ScrollProgress::new(830.0, 1000.0, 200.0);
+        assert_eq!(below.overscroll, 30.0);
+        assert_eq!(below.progress, 1.0);
+
+        let within = ScrollProgress::new(400.0, 1000.0, 200.0);
+        assert_eq!(within.overscroll, 0.0);
+        assert_eq!(within.progress, 0.5);
+    }
+
+    #[test]
+    fn short_scroll_distance_is_held_at_zero() {
+        // 1000px of content in a 950px viewport only scrolls 50px
+        let mut short = ScrollProgress::new(50.0, 1000.0, 950.0);
+        assert!(!hold_unscrollable(&mut short, 100.0));
+        assert_eq!(short.progress, 0.0);
+
+        let mut long = ScrollProgress::new(400.0, 1000.0, 200.0);
+        assert!(hold_unscrollable(&mut long, 100.0));
+        assert_eq!(long.progress, 0.5);
+    }
+
+    #[test]
+    fn unscrollable_content_is_complete_without_a_minimum() {
+        let mut fits = ScrollProgress::new(0.0, 400.0, 600.0);
+
+        assert!(hold_unscrollable(&mut fits, 0.0));
+        assert_eq!(fits.progress, 1.0);
+    }
+
+    #[test]
+    fn remap_clamps_input_and_interpolates_output() {
+        assert_eq!(at(0.1).remap(0.2, 0.8, 100.0, -50.0), 100.0);
+        assert!((at(0.5).remap(0.2, 0.8, 100.0, -50.0) - 25.0).abs() < 1e-9);
+        assert_eq!(at(0.9).remap(0.2, 0.8, 100.0, -50.0), -50.0);
+        assert_eq!(at(0.75).remap(0.0, 1.0, 0.0, 360.0), 270.0);
+
+        let eased = at(0.5).eased_remap(0.0, 1.0, 0.0, 100.0, EasingFunction::EaseIn);
+        assert_eq!(eased, 25.0);
+    }
+
+    #[test]
+    fn percent_scales_and_rounds() {
+        assert_eq!(at(0.5).percent(), 50.0);
+        assert_eq!(at(0.12345).percent_rounded(1), 12.3);
+        assert_eq!(at(0.12375).percent_rounded(2), 12.38);
+        assert_eq!(at(0.126).percent_rounded(0), 13.0);
+    }
+
+    #[test]
+    fn approx_eq_tolerates_rounding_but_not_movement() {
+        let progress = ScrollProgress::new(0.3, 1.0, 0.0);
+        let rounded = ScrollProgress::new(0.1 + 0.2, 1.0, 0.0);
+
+        assert_ne!(progress, rounded);
+        assert!(progress.approx_eq(&rounded, 1e-9));
+        assert!(!progress.approx_eq(&ScrollProgress::new(0.31, 1.0, 0.0), 1e-9));
+    }
+
+    #[test]
+    fn display_summarizes_progress_for_logs() {
+        let progress = ScrollProgress::new(320.0, 1120.0, 360.0);
+
+        assert_eq!(progress.to_string(), "p=0.42 (y=320/760, vp=360)");
+    }
+
+    #[test]
+    fn direction_follows_scroll_position() {
+        let start = ScrollProgress::new(300.0, 1000.0, 200.0);
+        let further = ScrollProgress::new(400.0, 1000.0, 200.0);
+
+        assert_eq!(
+            ScrollDirection::between(&start, &further),
+            Some(ScrollDirection::Down)
+        );
+        assert_eq!(
+            ScrollDirection::between(&further, &start),
+            Some(ScrollDirection::Up)
+        );
+        assert_eq!(ScrollDirection::between(&start, &start), None);
+    }
+
+    #[test]
+    fn resize_has_no_direction() {
+        // Progress jumps as the content shrinks, but the user didn't scroll
+        let before = ScrollProgress::new(300.0, 1000.0, 200.0);
+        let after = ScrollProgress::new(300.0, 500.0, 200.0);
+
+        assert!(after.progress > before.progress);
+        assert_eq!(ScrollDirection::between(&before, &after), None);
+    }
+
+    #[test]
+    fn scroll_delta_is_signed_and_zero_on_resize() {
+        let start = ScrollProgress::new(300.0, 1000.0, 200.0);
+        let further = ScrollProgress::new(340.0, 1000.0, 200.0);
+        let resized = ScrollProgress::new(340.0, 1000.0, 300.0);
+
+        assert_eq!(further.scroll_delta(&start), 40.0);
+        assert_eq!(start.scroll_delta(&further), -40.0);
+        assert_eq!(resized.scroll_delta(&start), 0.0);
+    }
+
+    #[test]
+    fn two_d_progress_tracks_each_axis_independently() {
+        // 1000px wide with a 200px viewport, 600px tall with a 400px viewport
+        let progress = Scroll2DProgress::new(400.0, 1000.0, 200.0, 200.0, 600.0, 400.0);
+
+        assert_eq!(progress.x, 0.5);
+        assert_eq!(progress.y, 1.0);
+        assert_eq!(progress.horizontal().progress, progress.x);
+        assert_eq!(progress.vertical().progress, progress.y);
+    }
+
+    #[test]
+    fn offsets_resolve_against_client_height() {
+        assert_eq!(OffsetUnit::Pixels(64.0).resolve(800.0), 64.0);
+        assert_eq!(OffsetUnit::Percent(10.0).resolve(800.0), 80.0);
+        assert_eq!(OffsetUnit::default().resolve(800.0), 0.0);
+    }
+
+    #[test]
+    fn max_scroll_is_never_negative() {
+        assert_eq!(ScrollProgress::new(0.0, 1000.0, 400.0).max_scroll(), 600.0);
+        assert_eq!(ScrollProgress::new(0.0, 300.0, 400.0).max_scroll(), 0.0);
+    }
+
+    #[test]
+    fn counter_lands_exactly_on_target() {
+        let counter =
+            |progress: f64| at(progress).counter_value(0.2, 0.6, 1234, EasingFunction::Linear);
+        assert_eq!(counter(0.1), 0);
+        assert_eq!(counter(0.4), 617);
+        assert_eq!(counter(0.599999), 1233);
+        assert_eq!(counter(0.6), 1234);
+        assert_eq!(counter(0.9), 1234);
+
+        // Expo only approaches 1.0 but is pinned at the end
+        assert_eq!(
+            at(1.0).counter_value(0.0, 1.0, 1234, EasingFunction::EaseOutExpo),
+            1234
+        );
+        assert_eq!(
+            at(1.0).counter_value(0.0, 1.0, -50, EasingFunction::Linear),
+            -50
+        );
+    }
+
+    #[test]
+    fn thousands_are_grouped() {
+        assert_eq!(format_thousands(0, ','), "0");
+        assert_eq!(format_thousands(999, ','), "999");
+        assert_eq!(format_thousands(1234, ','), "1,234");
+        assert_eq!(format_thousands(1234567, ' '), "1 234 567");
+        assert_eq!(format_thousands(-100000, '.'), "-100.000");
+        assert_eq!(
+            format_thousands(i64::MIN, ','),
+            "-9,223,372,036,854,775,808"
+        );
+    }
+
+    #[test]
+    fn frame_index_covers_every_frame_and_clamps() {
+        assert_eq!(frame_index_at(0.0, 100), 0);
+        assert_eq!(frame_index_at(0.5, 100), 50);
+        assert_eq!(frame_index_at(0.999, 100), 99);
+        assert_eq!(frame_index_at(1.0, 100), 99);
+        assert_eq!(frame_index_at(-0.2, 100), 0);
+        assert_eq!(frame_index_at(1.4, 100), 99);
+        assert_eq!(frame_index_at(0.7, 1), 0);
+        assert_eq!(frame_index_at(0.7, 0), 0);
+    }
+
+    #[test]
+    fn supports_scroll_timeline_builds_for_the_host() {
+        // Only the browser can answer the query, but the `web_sys::css` binding it goes through
+        // has to exist for this target too
+        let query: fn() -> bool = supports_scroll_timeline;
+        let _ = query;
+    }
+
+    #[test]
+    fn is_scrollable_only_when_content_overflows() {
+        assert!(ScrollProgress::new(0.0, 1000.0, 400.0).is_scrollable());
+        assert!(!ScrollProgress::new(0.0, 400.0, 400.0).is_scrollable());
+        assert!(!ScrollProgress::new(0.0, 300.0, 400.0).is_scrollable());
+    }
+
+    #[test]
+    fn range_tracker_reports_enter_then_exit() {
+        let mut tracker = RangeTracker::new(0.4, 0.6);
+
+        assert_eq!(tracker.update(0.1), None);
+        assert_eq!(tracker.update(0.5), Some(RangeEvent::Enter));
+        assert_eq!(tracker.update(0.55), None);
+        assert_eq!(tracker.update(0.7), Some(RangeEvent::Exit));
+        assert_eq!(tracker.update(0.8), None);
+    }
+
+    #[test]
+    fn range_tracker_reports_fast_jumps_across_the_range() {
+        let mut tracker = RangeTracker::new(0.4, 0.6);
+        tracker.update(0.1);
+
+        // Entered and exited within one event, in both directions
+        assert_eq!(tracker.update(0.9), Some(RangeEvent::Crossed));
+        assert_eq!(tracker.update(0.2), Some(RangeEvent::Crossed));
+
+        let crossed = RangeEvent::Crossed;
+        assert!(crossed.is_enter() && crossed.is_exit());
+    }
+
+    #[test]
+    fn crossing_tracker_fires_on_requested_direction_and_rearms() {
+        let mut tracker = CrossingTracker::new(0.4, ScrollDirection::Down);
+        assert!(!tracker.update(0.1));
+        assert!(!tracker.update(0.3));
+        assert!(tracker.update(0.45));
+        assert!(!tracker.update(0.6));
+        // Scrolling back up re-arms without firing
+        assert!(!tracker.update(0.2));
+        assert!(tracker.update(0.9));
+
+        let mut tracker = CrossingTracker::new(0.4, ScrollDirection::Up);
+        assert!(!tracker.update(0.1));
+        assert!(!tracker.update(0.5));
+        assert!(tracker.update(0.39));
+    }
+
+    #[test]
+    fn crossing_tracker_never_fires_for_the_starting_side() {
+        let mut tracker = CrossingTracker::new(0.4, ScrollDirection::Down);
+        assert!(!tracker.update(0.8));
+        assert!(!tracker.update(0.9));
+    }
+
+    #[test]
+    fn range_tracker_priming_matches_run_straight_away() {
+        // Starting in range counts as entering, but nothing has been exited yet
+        let mut tracker = RangeTracker::new(0.0, 0.5);
+        assert_eq!(tracker.update(0.2), Some(RangeEvent::Enter));
+
+        // Starting past the range is neither
+        let mut tracker = RangeTracker::new(0.0, 0.5);
+        assert_eq!(tracker.update(0.8), None);
+        assert_eq!(tracker.update(0.3), Some(RangeEvent::Enter));
+    }
+
+    #[test]
+    fn keyboard_keys_map_to_scroll_actions() {
+        assert_eq!(
+            KeyboardScroll::from_key("PageDown", 600.0),
+            Some(KeyboardScroll::By(600.0))
+        );
+        assert_eq!(
+            KeyboardScroll::from_key("PageUp", 600.0),
+            Some(KeyboardScroll::By(-600.0))
+        );
+        assert_eq!(
+            KeyboardScroll::from_key("End", 600.0),
+            Some(KeyboardScroll::ToProgress(1.0))
+        );
+        assert_eq!(KeyboardScroll::from_key("a", 600.0), None);
+    }
+
+    #[test]
+    fn pixels_and_progress_convert_both_ways() {
+        // 800px to scroll under a 64px header
+        assert_eq!(progress_to_scroll_top(0.5, 64.0, 800.0), 336.0);
+        assert_eq!(scroll_top_to_progress(336.0, 64.0, 800.0), 0.5);
+
+        for progress in [0.0, 0.123, 0.75, 1.0] {
+            let pixels = progress_to_scroll_top(progress, 64.0, 800.0);
+            assert!((scroll_top_to_progress(pixels, 64.0, 800.0) - progress).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn pixels_to_progress_clamps_and_handles_unscrollable() {
+        assert_eq!(scroll_top_to_progress(-100.0, 0.0, 800.0), 0.0);
+        assert_eq!(scroll_top_to_progress(900.0, 0.0, 800.0), 1.0);
+        assert_eq!(scroll_top_to_progress(0.0, 0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn wheel_deltas_normalize_to_pixels() {
+        assert_eq!(normalize_wheel_delta(120.0, 0, 800.0), 120.0);
+        assert_eq!(normalize_wheel_delta(3.0, 1, 800.0), 48.0);
+        assert_eq!(normalize_wheel_delta(-1.0, 2, 800.0), -800.0);
+    }
+
+    #[test]
+    fn drag_reports_deltas_and_tells_clicks_from_drags() {
+        let mut drag = DragScroll::new(1, 100.0, 100.0);
+
+        assert_eq!(drag.move_to(103.0, 104.0), (3.0, 4.0));
+        assert!(!drag.is_click(), "5px of travel is a drag");
+
+        let mut click = DragScroll::new(1, 100.0, 100.0);
+        click.move_to(101.0, 100.0);
+        click.move_to(100.0, 100.0);
+        assert!(click.is_click());
+    }
+
+    #[test]
+    fn element_progress_spans_entering_to_leaving() {
+        // 600px visible area starting at 100px, 200px tall element
+        assert_eq!(element_progress(800.0, 200.0, 100.0, 600.0), 0.0);
+        assert_eq!(element_progress(300.0, 200.0, 100.0, 600.0), 0.5);
+        assert_eq!(element_progress(-100.0, 200.0, 100.0, 600.0), 1.0);
+        assert_eq!(element_progress(-500.0, 200.0, 100.0, 600.0), 1.0);
+    }
+
+    #[test]
+    fn active_ranges_contain_the_progress() {
+        let range = |name: &str, from: f64, to: f64| NamedRange {
+            tag: None,
+            name: name.to_owned(),
+            from,
+            to,
+        };
+        let ranges = [
+            range("intro", 0.0, 0.3),
+            range("chart", 0.2, 0.6),
+            range("outro", 0.7, 1.0),
+        ];
+
+        assert_eq!(active_range_names(&ranges, &at(0.25)), ["intro", "chart"]);
+        assert_eq!(active_range_names(&ranges, &at(0.5)), ["chart"]);
+        assert!(active_range_names(&ranges, &at(0.65)).is_empty());
+    }
+
+    #[test]
+    fn viewport_progress_runs_from_entering_to_leaving() {
+        // 800px viewport, 200px tall element
+        let entering = viewport_progress(800.0, 200.0, 0.0, 800.0);
+        assert_eq!(entering.progress, 0.0);
+        assert_eq!(entering.scroll_y, 0.0);
+        assert_eq!(entering.max_scroll(), 1000.0);
+
+        // Centered on the middle of the viewport
+        let centered = viewport_progress(300.0, 200.0, 0.0, 800.0);
+        assert_eq!(centered.progress, 0.5);
+        assert_eq!(centered.scroll_y, 500.0);
+        assert_eq!(centered.scroll_delta(&entering), 500.0);
+
+        assert_eq!(viewport_progress(-200.0, 200.0, 0.0, 800.0).progress, 1.0);
+    }
+
+    #[test]
+    fn reading_fraction_covers_the_area_between_header_and_footer() {
+        // 64px header and 36px footer on an 800px window, 2000px article
+        assert_eq!(reading_fraction(300.0, 2000.0, 64.0, 764.0), 0.0);
+        assert_eq!(reading_fraction(64.0, 2000.0, 64.0, 764.0), 0.0);
+        assert_eq!(reading_fraction(-586.0, 2000.0, 64.0, 764.0), 0.5);
+        assert_eq!(reading_fraction(-1236.0, 2000.0, 64.0, 764.0), 1.0);
+        assert_eq!(reading_fraction(-3000.0, 2000.0, 64.0, 764.0), 1.0);
+
+        // A short article is read once it's fully above the footer
+        assert_eq!(reading_fraction(600.0, 300.0, 64.0, 764.0), 0.0);
+        assert_eq!(reading_fraction(400.0, 300.0, 64.0, 764.0), 1.0);
+    }
 }
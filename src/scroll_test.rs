@@ -1,7 +1,6 @@
 use crate::scroll_storyteller::{create_element_storyteller_with_config, ScrollStorytellerConfig};
 use leptos::prelude::*;
 use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::HtmlElement;
 
 /// Simple test component to verify scroll functionality is working
 #[component]
@@ -19,30 +18,21 @@ pub fn ScrollTest() -> impl IntoView {
             let config = ScrollStorytellerConfig {
                 throttle_ms: 50, // Slower throttle for debugging
                 smooth_scroll: true,
-                offset_top: 0.0,
-                offset_bottom: 0.0,
                 run_straight_away: false,
                 resize_debounce_ms: 250,
+                ..Default::default()
             };
 
-            match create_element_storyteller_with_config(
-                container.clone().unchecked_into::<HtmlElement>(),
-                config,
-            ) {
+            match create_element_storyteller_with_config(container.clone(), config) {
                 Ok(storyteller) => {
                     web_sys::console::log_1(&"Storyteller created successfully!".into());
                     debug_info.set("Storyteller created - waiting for scroll".to_string());
 
                     // Simple scroll callback
                     storyteller.on_scroll(move |progress| {
-                        web_sys::console::log_1(
-                            &format!("SCROLL EVENT! Progress: {}", progress.progress).into(),
-                        );
+                        web_sys::console::log_1(&format!("SCROLL EVENT! {}", progress).into());
                         scroll_value.set(progress.progress);
-                        debug_info.set(format!(
-                            "Progress: {:.3} | ScrollY: {:.1} | Height: {:.1}",
-                            progress.progress, progress.scroll_y, progress.scroll_height
-                        ));
+                        debug_info.set(progress.to_string());
                     });
                 }
                 Err(e) => {
@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use serde_lite::{Deserialize, Serialize};
+use web_sys::Storage;
+
+use super::net::{from_json_lite, to_json_lite};
+
+// Both are unavailable (or throw on access) in some private browsing modes
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+pub(crate) fn session_storage() -> Option<Storage> {
+    web_sys::window()?.session_storage().ok().flatten()
+}
+
+fn set_item<T: Serialize + ?Sized>(storage: Option<Storage>, key: &str, value: &T) -> Result<()> {
+    let storage = storage.ok_or_else(|| anyhow!("Storage is unavailable"))?;
+    storage
+        .set_item(key, &to_json_lite(value)?)
+        // Throws when the quota is exceeded
+        .map_err(|error| anyhow!("Failed to write {}: {:?}", key, error))
+}
+
+fn get_item<T: Deserialize>(storage: Option<Storage>, key: &str) -> Option<T> {
+    let json = storage?.get_item(key).ok()??;
+    from_json_lite(&json).ok()
+}
+
+/// Saves `value` as JSON in `localStorage` under `key`
+pub fn set_local<T: Serialize + ?Sized>(key: &str, value: &T) -> Result<()> {
+    set_item(local_storage(), key, value)
+}
+
+/// Reads `key` from `localStorage`, `None` if it's missing, doesn't parse as `T`, or storage is
+/// unavailable
+pub fn get_local<T: Deserialize>(key: &str) -> Option<T> {
+    get_item(local_storage(), key)
+}
+
+pub fn remove_local(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
+
+/// Saves `value` as JSON in `sessionStorage` under `key`
+pub fn set_session<T: Serialize + ?Sized>(key: &str, value: &T) -> Result<()> {
+    set_item(session_storage(), key, value)
+}
+
+/// Reads `key` from `sessionStorage`, `None` if it's missing, doesn't parse as `T`, or storage is
+/// unavailable
+pub fn get_session<T: Deserialize>(key: &str) -> Option<T> {
+    get_item(session_storage(), key)
+}
+
+pub fn remove_session(key: &str) {
+    if let Some(storage) = session_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
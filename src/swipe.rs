@@ -0,0 +1,207 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{AddEventListenerOptions, HtmlElement, TouchEvent};
+
+use crate::cleanup::on_cleanup_local;
+
+const SWIPE_EVENTS: [&str; 4] = ["touchstart", "touchmove", "touchend", "touchcancel"];
+
+/// Which way the finger moved
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SwipeConfig {
+    /// Distance in px along the main axis below which a touch is a tap rather than a swipe
+    pub min_distance_px: f64,
+    /// Average speed in px/ms below which a touch is a slow drag rather than a swipe
+    pub min_velocity: f64,
+}
+
+impl Default for SwipeConfig {
+    fn default() -> Self {
+        Self {
+            min_distance_px: 50.0,
+            min_velocity: 0.3,
+        }
+    }
+}
+
+type SwipeHandler = Box<dyn Fn(SwipeDirection, f64)>;
+
+/// Where and when a single-finger touch started, and where it's been since
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct TouchTrack {
+    start_x: f64,
+    start_y: f64,
+    start_time: f64,
+    last_x: f64,
+    last_y: f64,
+}
+
+/// Detects single-finger swipes on an element, reporting their direction and average velocity
+/// in px/ms. Listeners are removed when the calling component unmounts
+pub struct SwipeDetector {
+    callbacks: Rc<RefCell<Vec<SwipeHandler>>>,
+}
+
+impl SwipeDetector {
+    pub fn new(element: &HtmlElement, config: Option<SwipeConfig>) -> Result<Self, JsValue> {
+        let config = config.unwrap_or_default();
+        let callbacks: Rc<RefCell<Vec<SwipeHandler>>> = Rc::new(RefCell::new(Vec::new()));
+        let track = Cell::new(None::<TouchTrack>);
+
+        let swipe_callbacks = callbacks.clone();
+        let on_touch = Closure::wrap(Box::new(move |event: TouchEvent| {
+            let touches = event.touches();
+
+            match event.type_().as_str() {
+                "touchstart" => {
+                    // A second finger makes it a pinch or similar, not a swipe
+                    let (Some(touch), 1) = (touches.get(0), touches.length()) else {
+                        track.set(None);
+                        return;
+                    };
+                    let (x, y) = (touch.client_x() as f64, touch.client_y() as f64);
+                    track.set(Some(TouchTrack {
+                        start_x: x,
+                        start_y: y,
+                        start_time: event.time_stamp(),
+                        last_x: x,
+                        last_y: y,
+                    }));
+                }
+                "touchmove" => {
+                    let (Some(mut current), Some(touch)) = (track.get(), touches.get(0)) else {
+                        return;
+                    };
+                    current.last_x = touch.client_x() as f64;
+                    current.last_y = touch.client_y() as f64;
+                    track.set(Some(current));
+                }
+                "touchend" => {
+                    let Some(current) = track.take() else {
+                        return;
+                    };
+
+                    let swipe = classify_swipe(
+                        current.last_x - current.start_x,
+                        current.last_y - current.start_y,
+                        event.time_stamp() - current.start_time,
+                        &config,
+                    );
+                    if let Some((direction, velocity)) = swipe {
+                        for callback in swipe_callbacks.borrow().iter() {
+                            callback(direction, velocity);
+                        }
+                    }
+                }
+                // touchcancel, the browser took the gesture over
+                _ => track.set(None),
+            }
+        }) as Box<dyn FnMut(TouchEvent)>);
+
+        // Passive so native scrolling isn't held up waiting on the listener
+        let options = AddEventListenerOptions::new();
+        options.set_passive(true);
+        for event in SWIPE_EVENTS {
+            element.add_event_listener_with_callback_and_add_event_listener_options(
+                event,
+                on_touch.as_ref().unchecked_ref(),
+                &options,
+            )?;
+        }
+
+        let element = element.clone();
+        on_cleanup_local(move || {
+            for event in SWIPE_EVENTS {
+                let _ = element
+                    .remove_event_listener_with_callback(event, on_touch.as_ref().unchecked_ref());
+            }
+        });
+
+        Ok(Self { callbacks })
+    }
+
+    /// Calls `callback` with the direction and average velocity in px/ms of every swipe
+    pub fn on_swipe<F>(&self, callback: F)
+    where
+        F: Fn(SwipeDirection, f64) + 'static,
+    {
+        self.callbacks.borrow_mut().push(Box::new(callback));
+    }
+}
+
+/// Turns a touch that moved `(dx, dy)` over `duration_ms` into a swipe along its main axis, or
+/// `None` if it was too short or too slow to count
+fn classify_swipe(
+    dx: f64,
+    dy: f64,
+    duration_ms: f64,
+    config: &SwipeConfig,
+) -> Option<(SwipeDirection, f64)> {
+    let (distance, direction) = if dx.abs() >= dy.abs() {
+        let direction = if dx > 0.0 {
+            SwipeDirection::Right
+        } else {
+            SwipeDirection::Left
+        };
+        (dx.abs(), direction)
+    } else {
+        let direction = if dy > 0.0 {
+            SwipeDirection::Down
+        } else {
+            SwipeDirection::Up
+        };
+        (dy.abs(), direction)
+    };
+
+    // Guard against a zero duration from coarse timestamps
+    let velocity = distance / duration_ms.max(1.0);
+    if distance < config.min_distance_px || velocity < config.min_velocity {
+        return None;
+    }
+
+    Some((direction, velocity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_swipe, SwipeConfig, SwipeDirection};
+
+    #[test]
+    fn swipe_follows_the_dominant_axis() {
+        let config = SwipeConfig::default();
+
+        assert_eq!(
+            classify_swipe(-120.0, 30.0, 200.0, &config),
+            Some((SwipeDirection::Left, 0.6))
+        );
+        assert_eq!(
+            classify_swipe(20.0, 100.0, 100.0, &config),
+            Some((SwipeDirection::Down, 1.0))
+        );
+    }
+
+    #[test]
+    fn taps_and_slow_drags_are_not_swipes() {
+        let config = SwipeConfig::default();
+
+        assert_eq!(classify_swipe(10.0, 5.0, 80.0, &config), None, "tap");
+        assert_eq!(
+            classify_swipe(0.0, -200.0, 2000.0, &config),
+            None,
+            "slow drag"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use leptos::prelude::{set_timeout_with_handle, TimeoutHandle};
+
+/// Wraps `f` so it runs at most once every `ms` milliseconds, dropping calls in between. The
+/// first call always runs
+pub fn throttle<T, F>(ms: u32, f: F) -> impl Fn(T)
+where
+    F: Fn(T) + 'static,
+{
+    let performance = web_sys::window().and_then(|window| window.performance());
+    let interval_ms = ms as f64;
+    let last_run_time = Cell::new(f64::NEG_INFINITY);
+
+    move |value: T| {
+        // Without a clock there's nothing to throttle against, so every call runs
+        if let Some(performance) = &performance
+            && !interval_elapsed(&last_run_time, performance.now(), interval_ms)
+        {
+            return;
+        }
+        f(value);
+    }
+}
+
+/// Wraps `f` so it only runs once calls stop for `ms` milliseconds, with the last call's value.
+/// Each call cancels the pending run and schedules a new one
+pub fn debounce<T, F>(ms: u32, f: F) -> impl Fn(T)
+where
+    T: 'static,
+    F: Fn(T) + 'static,
+{
+    let f = Rc::new(f);
+    let pending = Cell::new(None::<TimeoutHandle>);
+
+    move |value: T| {
+        if let Some(handle) = pending.take() {
+            handle.clear();
+        }
+
+        let f = f.clone();
+        pending
+            .set(set_timeout_with_handle(move || f(value), Duration::from_millis(ms as u64)).ok());
+    }
+}
+
+/// Records `now` and returns true if at least `interval_ms` has passed since the last recorded run
+fn interval_elapsed(last_run_time: &Cell<f64>, now: f64, interval_ms: f64) -> bool {
+    if now - last_run_time.get() < interval_ms {
+        return false;
+    }
+
+    last_run_time.set(now);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interval_elapsed;
+    use std::cell::Cell;
+
+    #[test]
+    fn throttle_interval_allows_first_run_then_waits() {
+        let last_run_time = Cell::new(f64::NEG_INFINITY);
+
+        assert!(interval_elapsed(&last_run_time, 0.0, 100.0));
+        assert!(!interval_elapsed(&last_run_time, 50.0, 100.0));
+        assert!(interval_elapsed(&last_run_time, 100.0, 100.0));
+        assert!(!interval_elapsed(&last_run_time, 150.0, 100.0));
+    }
+}
@@ -1,43 +1,118 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveTime};
 use serde_lite::{Deserialize, Error, Intermediate, Serialize};
 use std::borrow::Cow;
 use std::str::FromStr;
 
-// Wrapper around NaiveDate
-#[derive(PartialEq, Eq, Hash, Clone, Debug, serde::Serialize, serde::Deserialize)]
-pub struct NaiveDateWrapper(pub NaiveDate);
+use super::string_wrapper;
 
-impl Serialize for NaiveDateWrapper {
+string_wrapper! {
+    #[derive(Copy)]
+    NaiveDateWrapper(NaiveDate),
+    // ISO 8601 date (YYYY-MM-DD)
+    to_string: |date| date.format("%Y-%m-%d").to_string(),
+    from_str: NaiveDate::from_str,
+}
+
+// Wrapper around NaiveTime
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct NaiveTimeWrapper(pub NaiveTime);
+
+impl Serialize for NaiveTimeWrapper {
     fn serialize(&self) -> Result<Intermediate, Error> {
-        // Convert `NaiveDate` to ISO 8601 date string (YYYY-MM-DD)
-        let date_str = self.0.format("%Y-%m-%d").to_string();
-        Ok(Intermediate::String(Cow::Owned(date_str)))
+        // HH:MM:SS, with fractional seconds only when present
+        let time_str = self.0.format("%H:%M:%S%.f").to_string();
+        Ok(Intermediate::String(Cow::Owned(time_str)))
     }
 }
 
-impl Deserialize for NaiveDateWrapper {
+impl Deserialize for NaiveTimeWrapper {
     fn deserialize(intermediate: &Intermediate) -> Result<Self, Error> {
-        // Expect the intermediate to be a string and parse it to `NaiveDate`
-        if let Intermediate::String(date_str) = intermediate {
-            NaiveDate::from_str(&date_str)
-                .map(NaiveDateWrapper)
-                .map_err(|_| Error::custom("Invalid date format"))
+        // Accept both HH:MM:SS (optionally fractional) and HH:MM
+        if let Intermediate::String(time_str) = intermediate {
+            NaiveTime::parse_from_str(time_str, "%H:%M:%S%.f")
+                .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
+                .map(NaiveTimeWrapper)
+                .map_err(|_| Error::custom("Invalid time format"))
         } else {
-            Err(Error::custom("Expected a string for NaiveDate"))
+            Err(Error::custom("Expected a string for NaiveTime"))
         }
     }
 }
 
-// Conversion from NaiveDate to NaiveDateWrapper
-impl From<NaiveDate> for NaiveDateWrapper {
-    fn from(date: NaiveDate) -> Self {
-        NaiveDateWrapper(date)
+// Conversion from NaiveTime to NaiveTimeWrapper
+impl From<NaiveTime> for NaiveTimeWrapper {
+    fn from(time: NaiveTime) -> Self {
+        NaiveTimeWrapper(time)
     }
 }
 
-// Conversion from NaiveDateWrapper to NaiveDate
-impl From<NaiveDateWrapper> for NaiveDate {
-    fn from(wrapper: NaiveDateWrapper) -> Self {
+// Conversion from NaiveTimeWrapper to NaiveTime
+impl From<NaiveTimeWrapper> for NaiveTime {
+    fn from(wrapper: NaiveTimeWrapper) -> Self {
         wrapper.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(time: NaiveTime) -> NaiveTime {
+        let intermediate = NaiveTimeWrapper(time).serialize().unwrap();
+        NaiveTimeWrapper::deserialize(&intermediate).unwrap().into()
+    }
+
+    #[test]
+    fn midnight_round_trips() {
+        let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+
+        assert!(matches!(
+            NaiveTimeWrapper(midnight).serialize().unwrap(),
+            Intermediate::String(time_str) if time_str == "00:00:00"
+        ));
+        assert_eq!(round_trip(midnight), midnight);
+    }
+
+    #[test]
+    fn fractional_seconds_round_trip() {
+        let time = NaiveTime::from_hms_milli_opt(12, 34, 56, 789).unwrap();
+        assert_eq!(round_trip(time), time);
+    }
+
+    #[test]
+    fn accepts_hours_and_minutes() {
+        let intermediate = Intermediate::String(Cow::Borrowed("09:00"));
+
+        assert_eq!(
+            NaiveTimeWrapper::deserialize(&intermediate).unwrap().0,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn date_serializes_as_iso_8601() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let intermediate = NaiveDateWrapper(date).serialize().unwrap();
+
+        assert!(matches!(
+            &intermediate,
+            Intermediate::String(date_str) if date_str == "2024-02-29"
+        ));
+        assert_eq!(
+            NaiveDateWrapper::deserialize(&intermediate).unwrap().0,
+            date
+        );
+    }
+
+    #[test]
+    fn null_date_deserializes_to_none() {
+        #[derive(serde_lite::Deserialize)]
+        struct Row {
+            due: Option<NaiveDateWrapper>,
+        }
+
+        let intermediate: Intermediate = serde_json::from_str(r#"{"due": null}"#).unwrap();
+
+        assert_eq!(Row::deserialize(&intermediate).unwrap().due, None);
+    }
+}
@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use serde_lite::{Deserialize, Error, Intermediate, Map, Serialize};
+
+/// Wrapper around a `(latitude, longitude)` pair, serialized as `{"lat": .., "lng": ..}`
+///
+/// The other wrappers map to `Intermediate::String`. This one is the reference for types that
+/// map to an object: build an `Intermediate::Map` field by field when serializing, and look each
+/// field up (erroring when missing) when deserializing
+#[derive(PartialEq, Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct GeoPointWrapper(pub (f64, f64));
+
+impl Serialize for GeoPointWrapper {
+    fn serialize(&self) -> Result<Intermediate, Error> {
+        let (lat, lng) = self.0;
+
+        let mut map = Map::new();
+        map.insert(Cow::Borrowed("lat"), lat.serialize()?);
+        map.insert(Cow::Borrowed("lng"), lng.serialize()?);
+
+        Ok(Intermediate::Map(map))
+    }
+}
+
+impl Deserialize for GeoPointWrapper {
+    fn deserialize(intermediate: &Intermediate) -> Result<Self, Error> {
+        // Expect a map with numeric `lat` and `lng` fields
+        if let Intermediate::Map(map) = intermediate {
+            let field = |name: &str| {
+                map.get(name)
+                    .ok_or_else(|| Error::custom(format!("Missing field {} for GeoPoint", name)))
+                    .and_then(f64::deserialize)
+            };
+
+            Ok(GeoPointWrapper((field("lat")?, field("lng")?)))
+        } else {
+            Err(Error::custom("Expected a map for GeoPoint"))
+        }
+    }
+}
+
+// Conversion from (lat, lng) to GeoPointWrapper
+impl From<(f64, f64)> for GeoPointWrapper {
+    fn from(point: (f64, f64)) -> Self {
+        GeoPointWrapper(point)
+    }
+}
+
+// Conversion from GeoPointWrapper to (lat, lng)
+impl From<GeoPointWrapper> for (f64, f64) {
+    fn from(wrapper: GeoPointWrapper) -> Self {
+        wrapper.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_json(json: &str) -> Result<GeoPointWrapper, Error> {
+        let intermediate: Intermediate = serde_json::from_str(json).unwrap();
+        GeoPointWrapper::deserialize(&intermediate)
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let point = GeoPointWrapper((-33.8688, 151.2093));
+        let json = serde_json::to_string(&point.serialize().unwrap()).unwrap();
+
+        assert_eq!(from_json(&json).unwrap(), point);
+    }
+
+    #[test]
+    fn missing_field_is_an_error() {
+        assert!(from_json(r#"{"lat": 1.5}"#).is_err());
+    }
+
+    #[test]
+    fn wrong_types_are_errors() {
+        assert!(from_json(r#"{"lat": "north", "lng": 2.0}"#).is_err());
+        assert!(from_json(r#"[1.5, 2.0]"#).is_err());
+    }
+}
@@ -1,2 +1,61 @@
 pub mod chrono;
+pub mod geo;
 pub mod uuid;
+
+/// Declares a newtype around `$inner` that `serde_lite` (de)serializes as a string, with the
+/// usual derives and `From` conversions both ways. `to_string` turns `&$inner` into a `String`
+/// and `from_str` parses a `&str` back into a `Result<$inner, _>`. Extra attributes, e.g.
+/// `#[derive(Copy)]`, are applied to the struct
+macro_rules! string_wrapper {
+    (
+        $(#[$meta:meta])*
+        $name:ident($inner:ty),
+        to_string: $to_string:expr,
+        from_str: $from_str:expr $(,)?
+    ) => {
+        #[derive(PartialEq, Eq, Hash, Clone, Debug, serde::Serialize, serde::Deserialize)]
+        $(#[$meta])*
+        pub struct $name(pub $inner);
+
+        impl serde_lite::Serialize for $name {
+            fn serialize(&self) -> Result<serde_lite::Intermediate, serde_lite::Error> {
+                let to_string: fn(&$inner) -> String = $to_string;
+                Ok(serde_lite::Intermediate::String(std::borrow::Cow::Owned(
+                    to_string(&self.0),
+                )))
+            }
+        }
+
+        impl serde_lite::Deserialize for $name {
+            fn deserialize(
+                intermediate: &serde_lite::Intermediate,
+            ) -> Result<Self, serde_lite::Error> {
+                let serde_lite::Intermediate::String(value) = intermediate else {
+                    return Err(serde_lite::Error::custom(concat!(
+                        "Expected a string for ",
+                        stringify!($inner)
+                    )));
+                };
+
+                let from_str: fn(&str) -> Result<$inner, _> = $from_str;
+                from_str(value).map($name).map_err(|_| {
+                    serde_lite::Error::custom(concat!("Invalid ", stringify!($inner), " format"))
+                })
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(wrapper: $name) -> Self {
+                wrapper.0
+            }
+        }
+    };
+}
+
+pub(crate) use string_wrapper;
@@ -1,43 +1,45 @@
-use std::{borrow::Cow, str::FromStr};
+use std::str::FromStr;
 
-use serde_lite::{Deserialize, Error, Intermediate, Serialize};
 use uuid::Uuid;
 
-// Wrapper around Uuid
-#[derive(PartialEq, Eq, Hash, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
-pub struct UuidWrapper(pub Uuid);
+use super::string_wrapper;
 
-impl Serialize for UuidWrapper {
-    fn serialize(&self) -> Result<Intermediate, Error> {
-        // Convert `Uuid` to a hyphenated string format
-        let uuid_str = self.0.hyphenated().to_string();
-        Ok(Intermediate::String(Cow::Owned(uuid_str)))
-    }
+string_wrapper! {
+    #[derive(Copy, Default)]
+    UuidWrapper(Uuid),
+    to_string: |uuid| uuid.hyphenated().to_string(),
+    from_str: Uuid::from_str,
 }
 
-impl Deserialize for UuidWrapper {
-    fn deserialize(intermediate: &Intermediate) -> Result<Self, Error> {
-        // Expect the intermediate to be a string and parse it to `Uuid`
-        if let Intermediate::String(uuid_str) = intermediate {
-            Uuid::from_str(uuid_str)
-                .map(UuidWrapper)
-                .map_err(|_| Error::custom("Invalid UUID format"))
-        } else {
-            Err(Error::custom("Expected a string for Uuid"))
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_lite::{Deserialize, Error, Intermediate};
+
+    #[derive(serde_lite::Deserialize)]
+    struct Row {
+        id: Option<UuidWrapper>,
     }
-}
 
-// Conversion from Uuid to UuidWrapper
-impl From<Uuid> for UuidWrapper {
-    fn from(uuid: Uuid) -> Self {
-        UuidWrapper(uuid)
+    fn row_from_json(json: &str) -> Result<Row, Error> {
+        let intermediate: Intermediate = serde_json::from_str(json).unwrap();
+        Row::deserialize(&intermediate)
     }
-}
 
-// Conversion from UuidWrapper to Uuid
-impl From<UuidWrapper> for Uuid {
-    fn from(wrapper: UuidWrapper) -> Self {
-        wrapper.0
+    #[test]
+    fn null_deserializes_to_none() {
+        assert_eq!(row_from_json(r#"{"id": null}"#).unwrap().id, None);
+    }
+
+    #[test]
+    fn present_value_deserializes_to_some() {
+        let row = row_from_json(r#"{"id": "67e55044-10b1-426f-9247-bb680e5fe0c8"}"#).unwrap();
+
+        assert_eq!(
+            row.id,
+            Some(UuidWrapper(
+                Uuid::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()
+            ))
+        );
     }
 }
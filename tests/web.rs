@@ -0,0 +1,195 @@
+//! Headless browser tests for the DOM-driven parts of the crate, run with
+//! `wasm-pack test --headless --firefox` (or `--chrome`).
+//!
+//! Each test mounts a fixture into `document.body` — layout only happens for
+//! attached elements, so a truly detached container would measure 0px — and
+//! removes it again when done. Reactive state is created under a throwaway
+//! `Owner` the same way a component would own it.
+//!
+//! What's under test:
+//!   - `ScrollStoryteller` progress for a container driven through `scroll_top`.
+//!   - `on_enter_range` firing once per entry.
+//!   - `Intersectioner` handing every element its own id and once state, without
+//!     clashing with other instances observing the same element.
+
+#![cfg(target_arch = "wasm32")]
+
+use std::{cell::Cell, rc::Rc};
+
+use generik::{
+    intersection_observer::{IntersectionConfig, Intersectioner},
+    scroll_storyteller::{ScrollStoryteller, ScrollStorytellerConfig},
+};
+use leptos::prelude::{GetUntracked, Owner};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{Element, HtmlElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const VIEWPORT_PX: i32 = 200;
+const CONTENT_PX: i32 = 1000;
+
+fn document() -> web_sys::Document {
+    web_sys::window().unwrap().document().unwrap()
+}
+
+fn div(style: &str) -> HtmlElement {
+    let element = document()
+        .create_element("div")
+        .unwrap()
+        .unchecked_into::<HtmlElement>();
+    element.set_attribute("style", style).unwrap();
+    element
+}
+
+/// A `VIEWPORT_PX` tall scroll container holding `CONTENT_PX` of content, mounted into the body
+fn scroll_container() -> HtmlElement {
+    let container = div(&format!("height: {VIEWPORT_PX}px; overflow-y: auto;"));
+    container
+        .append_child(&div(&format!("height: {CONTENT_PX}px;")))
+        .unwrap();
+    document().body().unwrap().append_child(&container).unwrap();
+    container
+}
+
+/// Resolves after `ms`, long enough for observers to deliver their next report
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+fn storyteller_config() -> ScrollStorytellerConfig {
+    ScrollStorytellerConfig {
+        throttle_ms: 0,
+        run_straight_away: false,
+        css_scroll_timeline: false,
+        ..Default::default()
+    }
+}
+
+#[wasm_bindgen_test]
+fn storyteller_measures_scroll_top() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let container = scroll_container();
+        let storyteller =
+            ScrollStoryteller::new(container.clone(), Some(storyteller_config())).unwrap();
+
+        let progress = storyteller.progress().get_untracked();
+        assert_eq!(progress.scroll_height, CONTENT_PX as f64);
+        assert_eq!(progress.viewport_height, VIEWPORT_PX as f64);
+        assert_eq!(storyteller.max_scroll(), (CONTENT_PX - VIEWPORT_PX) as f64);
+
+        container.set_scroll_top(400);
+        storyteller.refresh();
+        assert_eq!(storyteller.progress().get_untracked().progress, 0.5);
+
+        container.set_scroll_top(CONTENT_PX);
+        storyteller.refresh();
+        assert_eq!(storyteller.progress().get_untracked().progress, 1.0);
+
+        container.remove();
+    });
+}
+
+#[wasm_bindgen_test]
+fn storyteller_enters_range_once_per_entry() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let container = scroll_container();
+        let storyteller =
+            ScrollStoryteller::new(container.clone(), Some(storyteller_config())).unwrap();
+
+        let entries = Rc::new(Cell::new(0));
+        let counter = entries.clone();
+        storyteller.on_enter_range(0.4, 0.6, move |_| counter.set(counter.get() + 1));
+
+        for (scroll_top, expected) in [(400, 1), (420, 1), (700, 1), (400, 2)] {
+            container.set_scroll_top(scroll_top);
+            storyteller.refresh();
+            assert_eq!(entries.get(), expected, "after scrolling to {scroll_top}");
+        }
+
+        container.remove();
+    });
+}
+
+#[wasm_bindgen_test]
+fn intersectioner_assigns_distinct_ids() {
+    let intersectioner = Intersectioner::new(IntersectionConfig::new());
+    let elements = (0..100).map(|_| div("height: 1px;")).collect::<Vec<_>>();
+
+    for element in &elements {
+        intersectioner.observe(element, true, || {});
+    }
+
+    // Each instance keeps its ids under its own `data-observer-id-<instance>` attribute
+    let ids = elements
+        .iter()
+        .map(|element| observer_ids(element))
+        .collect::<Vec<_>>();
+    let expected = (0..100).map(|id| vec![id.to_string()]).collect::<Vec<_>>();
+    assert_eq!(ids, expected);
+    assert_eq!(intersectioner.observed_count(), 100);
+}
+
+#[wasm_bindgen_test]
+fn intersectioners_observing_the_same_element_keep_their_callbacks() {
+    let first = Intersectioner::new(IntersectionConfig::new());
+    let second = Intersectioner::new(IntersectionConfig::new());
+    let shared = div("height: 1px;");
+    let other = div("height: 1px;");
+
+    first.observe(&other, false, || {});
+    first.observe(&shared, false, || {});
+    second.observe(&shared, false, || {});
+
+    assert_eq!(first.observed_count(), 2);
+    assert_eq!(second.observed_count(), 1);
+    assert_eq!(observer_ids(&shared).len(), 2);
+
+    second.unobserve(&shared);
+    assert_eq!(first.observed_count(), 2);
+    assert_eq!(second.observed_count(), 0);
+}
+
+fn observer_ids(element: &Element) -> Vec<String> {
+    element
+        .get_attribute_names()
+        .iter()
+        .filter_map(|name| name.as_string())
+        .filter(|name| name.starts_with("data-observer-id-"))
+        .filter_map(|name| element.get_attribute(&name))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+async fn intersectioner_once_fires_exactly_once() {
+    let intersectioner = Intersectioner::new(IntersectionConfig::new());
+    let element = div("height: 10px;");
+    document().body().unwrap().append_child(&element).unwrap();
+
+    let triggers = Rc::new(Cell::new(0));
+    let counter = triggers.clone();
+    intersectioner.observe(&element, true, move || counter.set(counter.get() + 1));
+
+    sleep(100).await;
+    assert_eq!(triggers.get(), 1);
+    assert!(intersectioner.has_triggered(&element));
+
+    // Leaving and re-entering the viewport doesn't fire again
+    element.style().set_property("display", "none").unwrap();
+    sleep(100).await;
+    element.style().remove_property("display").unwrap();
+    sleep(100).await;
+    assert_eq!(triggers.get(), 1);
+
+    element.remove();
+}